@@ -17,12 +17,14 @@ You should have received a copy of the GNU General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 use crate::map_glosser_error;
+use actix_session::Session;
 use actix_web::Error as AWError;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
 use actix_web::web;
 use actix_web::web::Data;
 use gkvocabdb::GlosserDb;
+use gkvocabdb::TagFilter;
 use regex::Regex;
 use serde::Deserialize;
 
@@ -32,12 +34,16 @@ pub struct HQVocabRequest {
     pub lower: Option<String>,
     pub upper: Option<String>,
     pub abbrev: Option<String>,
+    pub include_tags: Option<String>, //comma separated tag_ids
+    pub exclude_tags: Option<String>,
 }
 
 pub async fn hqvocab(
-    (info, req): (web::Query<HQVocabRequest>, HttpRequest),
+    (session, info, req): (Session, web::Query<HQVocabRequest>, HttpRequest),
 ) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    //the page is public, so fall back to the default course when there is no session
+    let course_id: u32 = session.get("course_id").unwrap_or(None).unwrap_or(1);
     let mut template = include_str!("hqvocab.html").to_string();
 
     // let mut rows = String::from("");
@@ -121,13 +127,14 @@ pub async fn hqvocab(
     // };
 
     let sort = info.sort.clone().unwrap_or_else(|| String::from("unit"));
+    let tags = TagFilter::from_lists(info.include_tags.as_deref(), info.exclude_tags.as_deref());
     let mut tx = db.begin_tx().await.map_err(map_glosser_error)?;
     for p in ["noun", "verb", "adjective", "other"] {
         let mut res = String::from("");
         let mut last_unit = 0;
 
         let hqv = tx
-            .get_hqvocab_column(p, lower, upper, &sort)
+            .get_hqvocab_column(course_id, p, lower, upper, &sort, &tags)
             .await
             .map_err(map_glosser_error)?;
         for w in hqv {
@@ -218,6 +218,121 @@ async fn import_text(
     }
 }
 
+async fn import_glosses(
+    (session, payload, params, req): (
+        Session,
+        Multipart,
+        web::Query<ImportGlossesRequest>,
+        HttpRequest,
+    ),
+) -> Result<HttpResponse> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        match get_xml_string(payload).await {
+            Ok((data, _title)) => {
+                let res =
+                    import_glosses::gkv_import_glosses(db.as_ref(), &data, params.format, &info)
+                        .await
+                        .map_err(map_glosser_error)?;
+                Ok(HttpResponse::Ok().json(res))
+            }
+            Err(e) => {
+                let res = ImportResponse {
+                    success: false,
+                    text_id: 0,
+                    words_inserted: 0,
+                    error: format!(
+                        "Error importing glosses: invalid utf8. Valid up to position: {}.",
+                        e.valid_up_to()
+                    ),
+                };
+                Ok(HttpResponse::Ok().json(res))
+            }
+        }
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn export_glosses(
+    (info, session, req): (web::Query<ExportGlossesRequest>, Session, HttpRequest),
+) -> Result<HttpResponse> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id: u32 = session.get("course_id").unwrap().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let course_id = if info.course_only.unwrap_or(false) {
+            Some(course_id)
+        } else {
+            None
+        };
+        let data = export_glosses::gkv_export_glosses(db.as_ref(), course_id, info.format)
+            .await
+            .map_err(map_glosser_error)?;
+
+        let (filename, content_type) = match info.format {
+            GlossaryFormat::Csv => ("glosses.csv", "text/csv"),
+            GlossaryFormat::Json => ("glosses.json", "application/json"),
+        };
+        let cd_header = ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(String::from(filename))],
+        };
+
+        Ok(HttpResponse::Ok()
+            .content_type(content_type)
+            .insert_header(cd_header)
+            .body(data))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn text_stats((session, req): (Session, HttpRequest)) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = text_stats::gkv_get_text_stats(db.as_ref(), course_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn export_text_stats((session, req): (Session, HttpRequest)) -> Result<HttpResponse> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id: u32 = session.get("course_id").unwrap().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let data = text_stats::gkv_export_text_stats(db.as_ref(), course_id)
+            .await
+            .map_err(map_glosser_error)?;
+
+        let cd_header = ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(String::from("textstats.csv"))],
+        };
+
+        Ok(HttpResponse::Ok()
+            .content_type("text/csv")
+            .insert_header(cd_header)
+            .body(data))
+    } else {
+        not_logged_in_response()
+    }
+}
+
 async fn get_xml_string(mut payload: Multipart) -> Result<(String, String), std::str::Utf8Error> {
     let mut ttbytes = web::BytesMut::new();
     let mut ddbytes = web::BytesMut::new();
@@ -233,148 +348,866 @@ async fn get_xml_string(mut payload: Multipart) -> Result<(String, String), std:
         }
         .to_string();
 
-        // Field in turn is stream of *Bytes* object
-        while let Some(chunk) = field.next().await {
-            let data = chunk.unwrap();
+        // Field in turn is stream of *Bytes* object
+        while let Some(chunk) = field.next().await {
+            let data = chunk.unwrap();
+
+            if name == "title" {
+                ttbytes.extend_from_slice(&data);
+            } else if name == "file" {
+                ddbytes.extend_from_slice(&data);
+            }
+        }
+    }
+
+    let title: String = match std::str::from_utf8(&ttbytes) {
+        Ok(xml_data) => xml_data.to_string(),
+        Err(e) => {
+            return Err(e); //utf8 error
+        }
+    };
+
+    let xml_string: String = match std::str::from_utf8(&ddbytes) {
+        Ok(xml_data) => xml_data.to_string(),
+        Err(e) => {
+            return Err(e); //utf8 error
+        }
+    };
+
+    Ok((xml_string, title))
+}
+
+async fn insert_pagebreak(
+    (info, session, req): (web::Form<PagebreakRequest>, Session, HttpRequest),
+) -> Result<HttpResponse> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let mut tx = db.begin_tx().await.unwrap();
+        tx.insert_pagebreak(info.word_id)
+            .await
+            .map_err(map_glosser_error)?;
+        tx.commit_tx().await.unwrap();
+
+        Ok(HttpResponse::Ok().json(InsertDeletePageBreak {
+            success: true,
+            word_id: info.word_id,
+        }))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn delete_pagebreak(
+    (info, session, req): (web::Form<PagebreakRequest>, Session, HttpRequest),
+) -> Result<HttpResponse> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let mut tx = db.begin_tx().await.unwrap();
+        tx.delete_pagebreak(info.word_id)
+            .await
+            .map_err(map_glosser_error)?;
+        tx.commit_tx().await.unwrap();
+
+        Ok(HttpResponse::Ok().json(InsertDeletePageBreak {
+            success: true,
+            word_id: info.word_id,
+        }))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn export_text(
+    (info, session, req): (web::Query<ExportRequest>, Session, HttpRequest),
+) -> Result<HttpResponse> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let bold_glosses = false;
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    //println!("host: {:?}", req.connection_info().host());
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let _text_ids_to_export = if !info.text_ids.contains(',') {
+            //if single text_id passed in we want to get all of its sibling texts and return them as a comma separated string
+            if let Ok(id) = info.text_ids.parse::<u32>() {
+                let mut tx = db.begin_tx().await.unwrap();
+                let text_ids = tx.get_sibling_texts(id).await.map_err(map_glosser_error)?;
+                tx.commit_tx().await.unwrap();
+                text_ids
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            } else {
+                String::from("") //error parsing single text_id string input
+            }
+        } else {
+            //if multiple texts passed in just use the as is
+            info.text_ids.clone()
+        };
+
+        //refusing because of unapproved glosses is an ExportError, sent back as a 409
+        let latex = export_text::gkv_export_texts_as_latex(
+            db.as_ref(),
+            /*lysias*/ //"133,134,135,136,137",
+            /*xenophon*/ //"129,130,131,132"
+            /*phaedrus*/ //"228,229,230,231,232,233,234,235,236,237,238,239,240,241,242,243,244,245,246,247,248,249,250,251,252,253,254,255,256,257,258,259,260,261,262,263,264,265,266,267,268,269", //"129,130,131,132", //info.text_ids.as_str(),
+            /*thuc*/ //"270,271,272,273,274,275,276,277,278,279,280,281,282,283,284,285,286,287,288,289,290,291,292,293,294,295",
+            /*ajax*/
+            //"296,297,298,299,300,301,302,303,304,305,306,307,308,309,310,311,312,313,314",
+            //&text_ids_to_export,
+            /*all*/ "133,134,135,136,137,129,130,131,132,228,229,230,231,232,233,234,235,236,237,238,239,240,241,242,243,244,245,246,247,248,249,250,251,252,253,254,255,256,257,258,259,260,261,262,263,264,265,266,267,268,269,270,271,272,273,274,275,276,277,278,279,280,281,282,283,284,285,286,287,288,289,290,291,292,293,294,295,296,297,298,299,300,301,302,303,304,305,306,307,308,309,310,311,312,313,314",
+            course_id,
+            bold_glosses,
+            &TagFilter::from_lists(info.include_tags.as_deref(), info.exclude_tags.as_deref()),
+            info.unapproved.unwrap_or_default(),
+        )
+        .await
+        .map_err(map_glosser_error)?;
+
+        let filename = "glosser_export.tex";
+        let cd_header = ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(String::from(filename))],
+        };
+
+        Ok(HttpResponse::Ok()
+            .content_type("application/x-latex")
+            .insert_header(cd_header)
+            .body(latex))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn update_or_add_gloss(
+    (session, post, req): (Session, web::Form<UpdateGlossRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_update_or_add_gloss(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn arrow_word_req(
+    (session, post, req): (Session, web::Form<ArrowWordRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_arrow_word(db.as_ref(), &post, &info, course_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn auto_arrow(
+    (session, post, req): (Session, web::Form<AutoArrowRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_auto_arrow(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn check_arrows((session, req): (Session, HttpRequest)) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = gkv_check_arrows(db.as_ref(), course_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn repair_arrows(
+    (session, post, req): (Session, web::Json<RepairArrowsRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_repair_arrows(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn arrow_history(
+    (session, info, req): (Session, web::Query<ArrowHistoryRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = gkv_get_arrow_history(db.as_ref(), course_id, info.gloss_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn revert_arrow(
+    (session, post, req): (Session, web::Form<RevertArrowRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_revert_arrow(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn clone_course(
+    (session, post, req): (Session, web::Json<CloneCourseRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_clone_course(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn course_baseline((session, req): (Session, HttpRequest)) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = gkv_get_course_baseline(db.as_ref(), course_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn set_course_baseline(
+    (session, post, req): (Session, web::Json<CourseBaselineRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_set_course_baseline(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn set_gloss(
+    (session, post, req): (Session, web::Form<SetGlossRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_update_gloss_id(db.as_ref(), post.gloss_id, post.word_id, &info, course_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn move_text(
+    (session, post, req): (Session, web::Form<MoveTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        gkv_move_text(db.as_ref(), post.text_id, post.step, &info, course_id)
+            .await
+            .map_err(map_glosser_error)?;
+        let res = MiscErrorResponse {
+            this_text: 1,
+            text_name: String::from(""),
+            words: [].to_vec(),
+            selected_id: None,
+            error: String::from("Success"),
+        };
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn update_text(
+    (session, post, req): (Session, web::Form<UpdateTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_update_text(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn delete_text(
+    (session, post, req): (Session, web::Form<DeleteTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_delete_text(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn split_text(
+    (session, post, req): (Session, web::Form<SplitTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_split_text(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn merge_texts(
+    (session, post, req): (Session, web::Form<MergeTextsRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_merge_texts(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn update_word(
+    (session, post, req): (Session, web::Form<UpdateWordRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_update_word(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn insert_word(
+    (session, post, req): (Session, web::Form<InsertWordRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_insert_word(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn delete_word(
+    (session, post, req): (Session, web::Form<DeleteWordRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_delete_word(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn flag_word(
+    (session, post, req): (Session, web::Form<FlagWordRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_flag_word(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn flagged_words(
+    (session, info, req): (Session, web::Query<FlaggedWordsRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let course_id = session.get("course_id").unwrap().unwrap();
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = gkv_get_flagged_words(db.as_ref(), course_id, info.text_id)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn nest_text(
+    (session, post, req): (Session, web::Form<NestTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_nest_text(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn attach_text(
+    (session, post, req): (Session, web::Form<AttachTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_attach_text(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn detach_text(
+    (session, post, req): (Session, web::Form<DetachTextRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_detach_text(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn update_container(
+    (session, post, req): (Session, web::Form<ContainerRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_update_container(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn delete_container(
+    (session, post, req): (Session, web::Form<DeleteContainerRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_delete_container(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn move_container(
+    (session, post, req): (Session, web::Form<MoveContainerRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_move_container(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn set_text_container(
+    (session, post, req): (Session, web::Form<TextContainerRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_set_text_container(db.as_ref(), course_id, &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct SetCourseRequest {
+    pub qtype: String,
+    pub course_id: u32,
+    pub success: bool,
+}
 
-            if name == "title" {
-                ttbytes.extend_from_slice(&data);
-            } else if name == "file" {
-                ddbytes.extend_from_slice(&data);
-            }
-        }
+async fn set_course_id(
+    (session, post, _req): (Session, web::Form<SetCourseRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let mut res = SetCourseRequest {
+        qtype: String::from("setcourseresponse"),
+        course_id: post.course_id,
+        success: false,
+    };
+    if session.insert("course_id", post.course_id).is_ok() {
+        res.success = true;
+        return Ok(HttpResponse::Ok().json(res));
     }
 
-    let title: String = match std::str::from_utf8(&ttbytes) {
-        Ok(xml_data) => xml_data.to_string(),
-        Err(e) => {
-            return Err(e); //utf8 error
-        }
-    };
+    Ok(HttpResponse::Ok().json(res))
+}
 
-    let xml_string: String = match std::str::from_utf8(&ddbytes) {
-        Ok(xml_data) => xml_data.to_string(),
-        Err(e) => {
-            return Err(e); //utf8 error
-        }
-    };
+async fn get_gloss(
+    (session, post, req): (Session, web::Form<GetGlossRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
 
-    Ok((xml_string, title))
+    let res = gkv_get_gloss(db.as_ref(), &post, course_id)
+        .await
+        .map_err(map_glosser_error)?;
+
+    Ok(HttpResponse::Ok().json(res))
 }
 
-async fn insert_pagebreak(
-    (info, session, req): (web::Form<PagebreakRequest>, Session, HttpRequest),
-) -> Result<HttpResponse> {
+async fn set_course_gloss(
+    (session, post, req): (Session, web::Form<CourseGlossRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+    let course_id = session.get("course_id").unwrap().unwrap();
 
-    if let Some(_user_id) = login::get_user_id(session) {
-        let mut tx = db.begin_tx().await.unwrap();
-        tx.insert_pagebreak(info.word_id)
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_set_course_gloss(db.as_ref(), course_id, &post, &info)
             .await
             .map_err(map_glosser_error)?;
-        tx.commit_tx().await.unwrap();
 
-        Ok(HttpResponse::Ok().json(InsertDeletePageBreak {
-            success: true,
-            word_id: info.word_id,
-        }))
+        Ok(HttpResponse::Ok().json(res))
     } else {
         not_logged_in_response()
     }
 }
 
-async fn delete_pagebreak(
-    (info, session, req): (web::Form<PagebreakRequest>, Session, HttpRequest),
-) -> Result<HttpResponse> {
+async fn get_tags(req: HttpRequest) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
 
-    if let Some(_user_id) = login::get_user_id(session) {
-        let mut tx = db.begin_tx().await.unwrap();
-        tx.delete_pagebreak(info.word_id)
+    let res = gkv_get_tags(db.as_ref()).await.map_err(map_glosser_error)?;
+
+    Ok(HttpResponse::Ok().json(res))
+}
+
+async fn update_tag(
+    (session, post, req): (Session, web::Form<UpdateTagRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
+
+        let res = gkv_update_tag(db.as_ref(), &post, &info)
             .await
             .map_err(map_glosser_error)?;
-        tx.commit_tx().await.unwrap();
 
-        Ok(HttpResponse::Ok().json(InsertDeletePageBreak {
-            success: true,
-            word_id: info.word_id,
-        }))
+        Ok(HttpResponse::Ok().json(res))
     } else {
         not_logged_in_response()
     }
 }
 
-async fn export_text(
-    (info, session, req): (web::Query<ExportRequest>, Session, HttpRequest),
-) -> Result<HttpResponse> {
+async fn tag_gloss(
+    (session, post, req): (Session, web::Form<GlossTagRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
-    let bold_glosses = false;
-    let course_id = session.get("course_id").unwrap().unwrap();
-
-    //println!("host: {:?}", req.connection_info().host());
 
-    if let Some(_user_id) = login::get_user_id(session) {
-        let _text_ids_to_export = if !info.text_ids.contains(',') {
-            //if single text_id passed in we want to get all of its sibling texts and return them as a comma separated string
-            if let Ok(id) = info.text_ids.parse::<u32>() {
-                let mut tx = db.begin_tx().await.unwrap();
-                let text_ids = tx.get_sibling_texts(id).await.map_err(map_glosser_error)?;
-                tx.commit_tx().await.unwrap();
-                text_ids
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join(",")
-            } else {
-                String::from("") //error parsing single text_id string input
-            }
-        } else {
-            //if multiple texts passed in just use the as is
-            info.text_ids.clone()
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
         };
 
-        if let Ok(latex) = export_text::gkv_export_texts_as_latex(
-            db.as_ref(),
-            /*lysias*/ //"133,134,135,136,137",
-            /*xenophon*/ //"129,130,131,132"
-            /*phaedrus*/ //"228,229,230,231,232,233,234,235,236,237,238,239,240,241,242,243,244,245,246,247,248,249,250,251,252,253,254,255,256,257,258,259,260,261,262,263,264,265,266,267,268,269", //"129,130,131,132", //info.text_ids.as_str(),
-            /*thuc*/ //"270,271,272,273,274,275,276,277,278,279,280,281,282,283,284,285,286,287,288,289,290,291,292,293,294,295",
-            /*ajax*/
-            //"296,297,298,299,300,301,302,303,304,305,306,307,308,309,310,311,312,313,314",
-            //&text_ids_to_export,
-            /*all*/ "133,134,135,136,137,129,130,131,132,228,229,230,231,232,233,234,235,236,237,238,239,240,241,242,243,244,245,246,247,248,249,250,251,252,253,254,255,256,257,258,259,260,261,262,263,264,265,266,267,268,269,270,271,272,273,274,275,276,277,278,279,280,281,282,283,284,285,286,287,288,289,290,291,292,293,294,295,296,297,298,299,300,301,302,303,304,305,306,307,308,309,310,311,312,313,314",
-            course_id,
-            bold_glosses,
-        )
-        .await
-        .map_err(map_glosser_error)
-        {
-            let filename = "glosser_export.tex";
-            let cd_header = ContentDisposition {
-                disposition: DispositionType::Attachment,
-                parameters: vec![DispositionParam::Filename(String::from(filename))],
-            };
-
-            Ok(HttpResponse::Ok()
-                .content_type("application/x-latex")
-                .insert_header(cd_header)
-                .body(latex))
-        } else {
-            let res = ImportResponse {
-                success: false,
-                text_id: 0,
-                words_inserted: 0,
-                error: String::from("Export failed"),
-            };
-            Ok(HttpResponse::Ok().json(res))
-        }
+        let res = gkv_tag_gloss(db.as_ref(), &post, &info)
+            .await
+            .map_err(map_glosser_error)?;
+
+        Ok(HttpResponse::Ok().json(res))
     } else {
         not_logged_in_response()
     }
 }
 
-async fn update_or_add_gloss(
-    (session, post, req): (Session, web::Form<UpdateGlossRequest>, HttpRequest),
+async fn set_gloss_status(
+    (session, post, req): (Session, web::Form<GlossStatusRequest>, HttpRequest),
 ) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
 
@@ -386,7 +1219,7 @@ async fn update_or_add_gloss(
             user_agent: get_user_agent(&req).unwrap_or("").to_string(),
         };
 
-        let res = gkv_update_or_add_gloss(db.as_ref(), &post, &info)
+        let res = gkv_set_gloss_status(db.as_ref(), &post, &info)
             .await
             .map_err(map_glosser_error)?;
 
@@ -396,10 +1229,25 @@ async fn update_or_add_gloss(
     }
 }
 
-async fn arrow_word_req(
-    (session, post, req): (Session, web::Form<ArrowWordRequest>, HttpRequest),
+async fn review_queue(
+    (session, info, req): (Session, web::Query<ReviewQueueRequest>, HttpRequest),
+) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = gkv_get_review_queue(db.as_ref(), info.status)
+            .await
+            .map_err(map_glosser_error)?;
+
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn set_gloss_units(
+    (session, post, req): (Session, web::Json<GlossUnitsRequest>, HttpRequest),
 ) -> Result<HttpResponse, AWError> {
-    let course_id = session.get("course_id").unwrap().unwrap();
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
 
     if let Some(user_id) = login::get_user_id(session) {
@@ -410,20 +1258,20 @@ async fn arrow_word_req(
             user_agent: get_user_agent(&req).unwrap_or("").to_string(),
         };
 
-        let res = gkv_arrow_word(db.as_ref(), &post, &info, course_id)
+        let res = gkv_set_gloss_units(db.as_ref(), &post, &info)
             .await
             .map_err(map_glosser_error)?;
+
         Ok(HttpResponse::Ok().json(res))
     } else {
         not_logged_in_response()
     }
 }
 
-async fn set_gloss(
-    (session, post, req): (Session, web::Form<SetGlossRequest>, HttpRequest),
+async fn replace_gloss(
+    (session, post, req): (Session, web::Form<ReplaceGlossRequest>, HttpRequest),
 ) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
-    let course_id = session.get("course_id").unwrap().unwrap();
 
     if let Some(user_id) = login::get_user_id(session) {
         let info = ConnectionInfo {
@@ -433,20 +1281,34 @@ async fn set_gloss(
             user_agent: get_user_agent(&req).unwrap_or("").to_string(),
         };
 
-        let res = gkv_update_gloss_id(db.as_ref(), post.gloss_id, post.word_id, &info, course_id)
+        let res = gkv_replace_gloss(db.as_ref(), &post, &info)
             .await
             .map_err(map_glosser_error)?;
+
         Ok(HttpResponse::Ok().json(res))
     } else {
         not_logged_in_response()
     }
 }
 
-async fn move_text(
-    (session, post, req): (Session, web::Form<MoveTextRequest>, HttpRequest),
+async fn deleted_glosses((session, req): (Session, HttpRequest)) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
+
+    if let Some(_user_id) = login::get_user_id(session) {
+        let res = gkv_get_deleted_glosses(db.as_ref())
+            .await
+            .map_err(map_glosser_error)?;
+
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
+}
+
+async fn restore_gloss(
+    (session, post, req): (Session, web::Form<RestoreGlossRequest>, HttpRequest),
 ) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
-    let course_id = session.get("course_id").unwrap().unwrap();
 
     if let Some(user_id) = login::get_user_id(session) {
         let info = ConnectionInfo {
@@ -456,55 +1318,58 @@ async fn move_text(
             user_agent: get_user_agent(&req).unwrap_or("").to_string(),
         };
 
-        gkv_move_text(db.as_ref(), post.text_id, post.step, &info, course_id)
+        let res = gkv_restore_gloss(db.as_ref(), &post, &info)
             .await
             .map_err(map_glosser_error)?;
-        let res = MiscErrorResponse {
-            this_text: 1,
-            text_name: String::from(""),
-            words: [].to_vec(),
-            selected_id: None,
-            error: String::from("Success"),
-        };
+
         Ok(HttpResponse::Ok().json(res))
     } else {
         not_logged_in_response()
     }
 }
 
-#[derive(Deserialize, Serialize)]
-pub struct SetCourseRequest {
-    pub qtype: String,
-    pub course_id: u32,
-    pub success: bool,
-}
+async fn purge_glosses((session, req): (Session, HttpRequest)) -> Result<HttpResponse, AWError> {
+    let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
 
-async fn set_course_id(
-    (session, post, _req): (Session, web::Form<SetCourseRequest>, HttpRequest),
-) -> Result<HttpResponse, AWError> {
-    let mut res = SetCourseRequest {
-        qtype: String::from("setcourseresponse"),
-        course_id: post.course_id,
-        success: false,
-    };
-    if session.insert("course_id", post.course_id).is_ok() {
-        res.success = true;
-        return Ok(HttpResponse::Ok().json(res));
-    }
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
 
-    Ok(HttpResponse::Ok().json(res))
+        let res = gkv_purge_glosses(db.as_ref(), &info)
+            .await
+            .map_err(map_glosser_error)?;
+
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
 }
 
-async fn get_gloss(
-    (post, req): (web::Form<GetGlossRequest>, HttpRequest),
+async fn recompute_sortalpha(
+    (session, req): (Session, HttpRequest),
 ) -> Result<HttpResponse, AWError> {
     let db = req.app_data::<Data<dyn GlosserDb>>().unwrap();
 
-    let res = gkv_get_gloss(db.as_ref(), &post)
-        .await
-        .map_err(map_glosser_error)?;
+    if let Some(user_id) = login::get_user_id(session) {
+        let info = ConnectionInfo {
+            user_id,
+            timestamp: get_timestamp(),
+            ip_address: get_ip(&req).unwrap_or_default(),
+            user_agent: get_user_agent(&req).unwrap_or("").to_string(),
+        };
 
-    Ok(HttpResponse::Ok().json(res))
+        let res = gkv_recompute_sortalpha(db.as_ref(), &info)
+            .await
+            .map_err(map_glosser_error)?;
+
+        Ok(HttpResponse::Ok().json(res))
+    } else {
+        not_logged_in_response()
+    }
 }
 
 async fn get_glosses(
@@ -657,6 +1522,16 @@ pub fn map_glosser_error(e: GlosserError) -> PhilologusError {
             name: String::from("import error"),
             error: format!("import error: {}", e),
         },
+        GlosserError::ExportError(e) => PhilologusError {
+            code: StatusCode::CONFLICT,
+            name: String::from("export error"),
+            error: format!("export error: {}", e),
+        },
+        GlosserError::RegexError(e) => PhilologusError {
+            code: StatusCode::BAD_REQUEST,
+            name: String::from("regex error"),
+            error: format!("regex error: {}", e),
+        },
         GlosserError::AuthenticationError => PhilologusError {
             code: StatusCode::INTERNAL_SERVER_ERROR,
             name: String::from("authentication error"),
@@ -697,9 +1572,7 @@ async fn get_db(db_path: &str) -> GlosserDbSqlite {
         .foreign_keys(true)
         .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
         .read_only(false)
-        .collation("PolytonicGreek", |l, r| {
-            l.to_lowercase().cmp(&r.to_lowercase())
-        });
+        .with_regexp();
 
     GlosserDbSqlite {
         db: SqlitePool::connect_with(options)
@@ -852,11 +1725,51 @@ fn config(cfg: &mut web::ServiceConfig) {
         .service(web::resource("/getgloss").route(web::post().to(get_gloss)))
         .service(web::resource("/hqvocab").route(web::get().to(hqvocab::hqvocab)))
         .service(web::resource("/arrowword").route(web::post().to(arrow_word_req)))
+        .service(web::resource("/autoarrow").route(web::post().to(auto_arrow)))
+        .service(web::resource("/checkarrows").route(web::get().to(check_arrows)))
+        .service(web::resource("/repairarrows").route(web::post().to(repair_arrows)))
+        .service(web::resource("/arrowhistory").route(web::get().to(arrow_history)))
+        .service(web::resource("/revertarrow").route(web::post().to(revert_arrow)))
+        .service(web::resource("/clonecourse").route(web::post().to(clone_course)))
+        .service(web::resource("/coursebaseline").route(web::get().to(course_baseline)))
+        .service(web::resource("/setcoursebaseline").route(web::post().to(set_course_baseline)))
         .service(web::resource("/setgloss").route(web::post().to(set_gloss)))
         .service(web::resource("/updategloss").route(web::post().to(update_or_add_gloss)))
+        .service(web::resource("/setcoursegloss").route(web::post().to(set_course_gloss)))
+        .service(web::resource("/gettags").route(web::get().to(get_tags)))
+        .service(web::resource("/updatetag").route(web::post().to(update_tag)))
+        .service(web::resource("/glosstag").route(web::post().to(tag_gloss)))
+        .service(web::resource("/setglossstatus").route(web::post().to(set_gloss_status)))
+        .service(web::resource("/reviewqueue").route(web::get().to(review_queue)))
+        .service(web::resource("/setglossunits").route(web::post().to(set_gloss_units)))
+        .service(web::resource("/replacegloss").route(web::post().to(replace_gloss)))
+        .service(web::resource("/deletedglosses").route(web::get().to(deleted_glosses)))
+        .service(web::resource("/restoregloss").route(web::post().to(restore_gloss)))
+        .service(web::resource("/purgeglosses").route(web::post().to(purge_glosses)))
+        .service(web::resource("/recomputesortalpha").route(web::post().to(recompute_sortalpha)))
         .service(web::resource("/importtext").route(web::post().to(import_text)))
         .service(web::resource("/exporttext").route(web::get().to(export_text)))
+        .service(web::resource("/importglosses").route(web::post().to(import_glosses)))
+        .service(web::resource("/exportglosses").route(web::get().to(export_glosses)))
+        .service(web::resource("/textstats").route(web::get().to(text_stats)))
+        .service(web::resource("/exporttextstats").route(web::get().to(export_text_stats)))
         .service(web::resource("/movetext").route(web::post().to(move_text)))
+        .service(web::resource("/updatetext").route(web::post().to(update_text)))
+        .service(web::resource("/deletetext").route(web::post().to(delete_text)))
+        .service(web::resource("/splittext").route(web::post().to(split_text)))
+        .service(web::resource("/mergetexts").route(web::post().to(merge_texts)))
+        .service(web::resource("/updateword").route(web::post().to(update_word)))
+        .service(web::resource("/insertword").route(web::post().to(insert_word)))
+        .service(web::resource("/deleteword").route(web::post().to(delete_word)))
+        .service(web::resource("/flagword").route(web::post().to(flag_word)))
+        .service(web::resource("/flaggedwords").route(web::get().to(flagged_words)))
+        .service(web::resource("/nesttext").route(web::post().to(nest_text)))
+        .service(web::resource("/updatecontainer").route(web::post().to(update_container)))
+        .service(web::resource("/deletecontainer").route(web::post().to(delete_container)))
+        .service(web::resource("/movecontainer").route(web::post().to(move_container)))
+        .service(web::resource("/settextcontainer").route(web::post().to(set_text_container)))
+        .service(web::resource("/attachtext").route(web::post().to(attach_text)))
+        .service(web::resource("/detachtext").route(web::post().to(detach_text)))
         .service(web::resource("/insertpagebreak").route(web::post().to(insert_pagebreak)))
         .service(web::resource("/deletepagebreak").route(web::post().to(delete_pagebreak)))
         .service(web::resource("/healthzzz").route(web::get().to(health_check)))
@@ -895,10 +1808,7 @@ mod tests {
             .expect("Could not connect to db.")
             .foreign_keys(true)
             .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-            .read_only(false)
-            .collation("PolytonicGreek", |l, r| {
-                l.to_lowercase().cmp(&r.to_lowercase())
-            });
+            .read_only(false);
 
         let db_pool = SqlitePool::connect_with(options)
             .await
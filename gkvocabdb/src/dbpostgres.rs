@@ -16,19 +16,40 @@ GNU General Public License for more details.
 You should have received a copy of the GNU General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
+use crate::ADDED_COLUMNS;
+use crate::ArrowHistoryEntry;
+use crate::ArrowedWordRow;
 use crate::AssignmentRow;
 use crate::AssignmentTree;
+use crate::AutoArrowRow;
 use crate::ConnectionInfo;
+use crate::CourseBaseline;
+use crate::CourseTextRow;
+use crate::DeletedGlossRow;
+use crate::FlaggedWordRow;
 use crate::GlossEntry;
 use crate::GlossOccurrence;
+use crate::GlossParts;
+use crate::GlossRegex;
+use crate::GlossReviewRow;
+use crate::GlossStatus;
+use crate::GlossaryRow;
 use crate::GlosserDb;
 use crate::GlosserDbTrx;
 use crate::GlosserError;
 use crate::LemmatizerRecord;
 use crate::SmallWord;
+use crate::Tag;
+use crate::TagFilter;
+use crate::TextArrowRow;
+use crate::TextGlossCount;
 use crate::TextWord;
 use crate::UpdateType;
 use crate::WordRow;
+use crate::WordType;
+use crate::greek::make_sortalpha;
+use crate::greek::sort_key;
+use crate::reorder_texts;
 use secrecy::ExposeSecret;
 use secrecy::Secret;
 use sqlx::Postgres;
@@ -38,7 +59,6 @@ use sqlx::postgres::PgPool;
 use sqlx::postgres::PgRow;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use unicode_normalization::UnicodeNormalization;
 
 /*
 pub async fn get_seq_by_prefix(pool: &SqlitePool, table:&str, prefix:&str) -> Result<u32, GlosserError> {
@@ -70,6 +90,10 @@ pub fn map_sqlx_error(err: sqlx::Error) -> GlosserError {
         sqlx::Error::Configuration(e) => {
             GlosserError::Database(format!("sqlx Configuration: {}", e))
         }
+        //invalid_regular_expression, from a user's search pattern
+        sqlx::Error::Database(e) if e.code().as_deref() == Some("2201B") => {
+            GlosserError::RegexError(e.message().to_string())
+        }
         sqlx::Error::Database(e) => GlosserError::Database(format!("sqlx Database: {}", e)),
         sqlx::Error::Io(e) => GlosserError::Database(format!("sqlx Io: {}", e)),
         sqlx::Error::Tls(e) => GlosserError::Database(format!("sqlx Tls: {}", e)),
@@ -96,6 +120,47 @@ pub fn map_sqlx_error(err: sqlx::Error) -> GlosserError {
     }
 }
 
+fn gloss_parts_from_row(rec: &PgRow) -> GlossParts {
+    GlossParts {
+        principal_parts: rec.get("principal_parts"),
+        genitive: rec.get("genitive"),
+        gender: rec.get("gender"),
+        terminations: rec.get("terminations"),
+    }
+}
+
+//a status we don't know about is treated as not yet approved
+fn gloss_status_from_row(rec: &PgRow) -> GlossStatus {
+    GlossStatus::from_i32(rec.get::<i32, _>("status")).unwrap_or(GlossStatus::Draft)
+}
+
+fn glossary_row(rec: &PgRow) -> GlossaryRow {
+    GlossaryRow {
+        gloss_id: Some(u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap()),
+        lemma: rec.get("lemma"),
+        sortalpha: rec.get("sortalpha"),
+        pos: rec.get("pos"),
+        def: rec.get("def"),
+        note: rec.get("note"),
+        unit: Some(u32::try_from(rec.get::<i32, _>("unit")).unwrap()),
+        status: Some(gloss_status_from_row(rec)),
+        principal_parts: rec.get("principal_parts"),
+        genitive: rec.get("genitive"),
+        gender: rec.get("gender"),
+        terminations: rec.get("terminations"),
+    }
+}
+
+fn gloss_review_row(rec: &PgRow) -> GlossReviewRow {
+    GlossReviewRow {
+        gloss_id: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+        lemma: rec.get("lemma"),
+        pos: rec.get("pos"),
+        def: rec.get("def"),
+        status: gloss_status_from_row(rec),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GlosserDbPostgres {
     pub db: PgPool,
@@ -252,15 +317,16 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
 
     async fn get_hqvocab_column(
         &mut self,
+        course_id: u32,
         pos: &str,
         lower_unit: u32,
         unit: u32,
         sort: &str,
+        tags: &TagFilter,
     ) -> Result<Vec<(String, u32, String)>, GlosserError> {
-        let course_id = 1;
         let s = match sort {
-            "alpha" => "sortalpha ASC",
-            _ => "unit, sortalpha ASC",
+            "alpha" => "sortalpha COLLATE \"C\" ASC, sortkey COLLATE \"C\" ASC",
+            _ => "unit, sortalpha COLLATE \"C\" ASC, sortkey COLLATE \"C\" ASC",
         };
         let p = match pos {
             "noun" => "pos = 'noun'",
@@ -269,10 +335,13 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             _ => "pos != 'noun' AND pos != 'verb' AND pos != 'adjective'",
         };
         let query = format!(
-            "SELECT lemma, unit, def FROM glosses a \
+            "SELECT lemma, unit, COALESCE(cg.def, a.def) AS def, principal_parts, genitive, gender, terminations FROM glosses a \
             LEFT JOIN arrowed_words d ON (a.gloss_id = d.gloss_id AND d.course_id = {course_id}) \
-            WHERE {} AND unit >= $1 AND unit <= $2 AND status = 1 ORDER BY {};",
-            p, s
+            LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {course_id}) \
+            WHERE {} AND unit >= $1 AND unit <= $2 AND status > 0{} ORDER BY {};",
+            p,
+            tags.sql("a.gloss_id"),
+            s
         );
 
         let words: Vec<(String, u32, String)> = sqlx::query(&query)
@@ -280,7 +349,7 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .bind(i32::try_from(unit).unwrap())
             .map(|rec: PgRow| {
                 (
-                    rec.get("lemma"),
+                    gloss_parts_from_row(&rec).display_lemma(rec.get("lemma")),
                     u32::try_from(rec.get::<i32, _>("unit")).unwrap(),
                     rec.get("def"),
                 )
@@ -433,6 +502,394 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         Ok(())
     }
 
+    async fn move_arrow(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+        word_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<(), GlosserError> {
+        let query = "INSERT INTO arrowed_words_history (course_id, gloss_id, word_id, updated, user_id, comment) \
+        SELECT course_id, gloss_id, word_id, updated, user_id, comment \
+        FROM arrowed_words \
+        WHERE course_id = $1 AND gloss_id = $2;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM arrowed_words WHERE course_id = $1 AND gloss_id = $2;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        if let Some(word_id) = word_id {
+            let query = "INSERT INTO arrowed_words (course_id, gloss_id, word_id, updated, user_id, comment) VALUES ($1, $2, $3, $4, $5, NULL);";
+            sqlx::query(query)
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(gloss_id).unwrap())
+                .bind(i32::try_from(word_id).unwrap())
+                .bind(info.timestamp)
+                .bind(i32::try_from(info.user_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+        Ok(())
+    }
+
+    async fn get_course_name(&mut self, course_id: u32) -> Result<Option<String>, GlosserError> {
+        let query = "SELECT name FROM courses WHERE course_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| rec.get("name"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_course_text_ids(&mut self, course_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT text_id FROM course_x_text WHERE course_id = $1 ORDER BY text_order;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("text_id")).unwrap())
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn clone_course(
+        &mut self,
+        source_course_id: u32,
+        name: &str,
+        text_ids: &[u32],
+        info: &ConnectionInfo,
+    ) -> Result<(u32, u64, u64, u64), GlosserError> {
+        //course 1 is inserted with an explicit id, so don't rely on the serial sequence
+        let query = "INSERT INTO courses (course_id, name) \
+        VALUES ((SELECT COALESCE(MAX(course_id), 0) + 1 FROM courses), $1) RETURNING course_id;";
+        let course_id: i32 = sqlx::query(query)
+            .bind(name)
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get(0);
+        //text_ids are u32s so they are safe to interpolate
+        let text_list = text_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = format!(
+            "INSERT INTO course_x_text (course_id, text_id, text_order, parent_id) \
+            SELECT $1, text_id, text_order, parent_id FROM course_x_text WHERE course_id = $2 AND text_id IN ({});",
+            if text_list.is_empty() {
+                "NULL"
+            } else {
+                &text_list
+            }
+        );
+        let texts = sqlx::query(&query)
+            .bind(course_id)
+            .bind(i32::try_from(source_course_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "INSERT INTO arrowed_words (course_id, gloss_id, word_id, updated, user_id, comment) \
+        SELECT $1, d.gloss_id, d.word_id, d.updated, d.user_id, d.comment FROM arrowed_words d \
+        LEFT JOIN words w ON d.word_id = w.word_id \
+        WHERE d.course_id = $2 \
+        AND (d.word_id = 1 OR w.text_id IN (SELECT text_id FROM course_x_text WHERE course_id = $1));";
+        let arrows = sqlx::query(query)
+            .bind(course_id)
+            .bind(i32::try_from(source_course_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "INSERT INTO course_x_gloss (course_id, gloss_id, def, note, updated, user_id) \
+        SELECT $1, gloss_id, def, note, updated, user_id FROM course_x_gloss WHERE course_id = $2;";
+        let glosses = sqlx::query(query)
+            .bind(course_id)
+            .bind(i32::try_from(source_course_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        for query in [
+            "INSERT INTO course_baselines (course_id, max_unit) \
+            SELECT $1, max_unit FROM course_baselines WHERE course_id = $2;",
+            "INSERT INTO course_known_glosses (course_id, gloss_id) \
+            SELECT $1, gloss_id FROM course_known_glosses WHERE course_id = $2;",
+            //containers are shared with the source course, as are their cloned texts' places in them
+            "INSERT INTO course_x_container (course_id, container_id, container_order) \
+            SELECT $1, container_id, container_order FROM course_x_container WHERE course_id = $2;",
+            "INSERT INTO container_x_text (course_id, text_id, container_id) \
+            SELECT $1, text_id, container_id FROM container_x_text WHERE course_id = $2 \
+            AND text_id IN (SELECT text_id FROM course_x_text WHERE course_id = $1);",
+        ] {
+            sqlx::query(query)
+                .bind(course_id)
+                .bind(i32::try_from(source_course_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.update_log_trx(
+            UpdateType::CloneCourse,
+            Some(course_id.into()),
+            None,
+            Some(course_id.into()),
+            format!(
+                "Cloned course ({}) from course ({}): {} texts, {} arrows, {} course glosses",
+                course_id, source_course_id, texts, arrows, glosses
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok((u32::try_from(course_id).unwrap(), texts, arrows, glosses))
+    }
+
+    async fn get_course_baseline(
+        &mut self,
+        course_id: u32,
+    ) -> Result<CourseBaseline, GlosserError> {
+        let query = "SELECT max_unit FROM course_baselines WHERE course_id = $1;";
+        let max_unit = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("max_unit")).unwrap())
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .unwrap_or(0);
+
+        let query =
+            "SELECT gloss_id FROM course_known_glosses WHERE course_id = $1 ORDER BY gloss_id;";
+        let gloss_ids = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap())
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(CourseBaseline {
+            max_unit,
+            gloss_ids,
+        })
+    }
+
+    async fn set_course_baseline(
+        &mut self,
+        course_id: u32,
+        baseline: &CourseBaseline,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "INSERT INTO course_baselines (course_id, max_unit) VALUES ($1, $2) \
+        ON CONFLICT (course_id) DO UPDATE SET max_unit = EXCLUDED.max_unit;";
+        let mut rows = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(baseline.max_unit).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "DELETE FROM course_known_glosses WHERE course_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "INSERT INTO course_known_glosses (course_id, gloss_id) VALUES ($1, $2) ON CONFLICT DO NOTHING;";
+        for gloss_id in &baseline.gloss_ids {
+            rows += sqlx::query(query)
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(*gloss_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected();
+        }
+
+        self.update_log_trx(
+            UpdateType::SetCourseBaseline,
+            None,
+            None,
+            Some(course_id.into()),
+            format!(
+                "Set baseline of course ({}) to units 1-{} and {} known glosses",
+                course_id,
+                baseline.max_unit,
+                baseline.gloss_ids.len()
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_known_gloss_ids(&mut self, course_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT gloss_id FROM glosses \
+        WHERE unit BETWEEN 1 AND (SELECT max_unit FROM course_baselines WHERE course_id = $1) \
+        UNION SELECT gloss_id FROM course_known_glosses WHERE course_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap())
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_arrowed_words(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<ArrowedWordRow>, GlosserError> {
+        let query = "SELECT d.gloss_id, d.word_id, w.gloss_id AS word_gloss_id, c.text_id IS NOT NULL AS in_course \
+        FROM arrowed_words d \
+        LEFT JOIN words w ON d.word_id = w.word_id \
+        LEFT JOIN course_x_text c ON (w.text_id = c.text_id AND c.course_id = d.course_id) \
+        WHERE d.course_id = $1 AND d.word_id != 1 \
+        ORDER BY d.gloss_id, d.word_id;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| ArrowedWordRow {
+                gloss_id: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                word_id: u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
+                word_gloss_id: rec
+                    .get::<Option<i32>, _>("word_gloss_id")
+                    .map(|id| u32::try_from(id).unwrap()),
+                in_course: rec.get("in_course"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_arrow_history(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+    ) -> Result<Vec<ArrowHistoryEntry>, GlosserError> {
+        let query = "SELECT h.history_id, h.word_id, w.word, h.updated, u.initials \
+        FROM arrowed_words_history h \
+        LEFT JOIN words w ON h.word_id = w.word_id \
+        LEFT JOIN users u ON h.user_id = u.user_id \
+        WHERE h.course_id = $1 AND h.gloss_id = $2 \
+        ORDER BY h.history_id;";
+        let mut history = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| ArrowHistoryEntry {
+                history_id: rec
+                    .get::<Option<i32>, _>("history_id")
+                    .map(|id| u32::try_from(id).unwrap()),
+                from_word_id: None,
+                word_id: rec
+                    .get::<Option<i32>, _>("word_id")
+                    .map(|id| u32::try_from(id).unwrap()),
+                word: rec.get("word"),
+                updated: rec.get::<Option<i32>, _>("updated").map(i64::from),
+                user: rec.get("initials"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "SELECT d.word_id, w.word, d.updated, u.initials \
+        FROM arrowed_words d \
+        LEFT JOIN words w ON d.word_id = w.word_id \
+        LEFT JOIN users u ON d.user_id = u.user_id \
+        WHERE d.course_id = $1 AND d.gloss_id = $2;";
+        let current = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| ArrowHistoryEntry {
+                history_id: None,
+                from_word_id: None,
+                word_id: rec
+                    .get::<Option<i32>, _>("word_id")
+                    .map(|id| u32::try_from(id).unwrap()),
+                word: rec.get("word"),
+                updated: rec.get::<Option<i32>, _>("updated").map(i64::from),
+                user: rec.get("initials"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        history.extend(current);
+
+        Ok(history)
+    }
+
+    async fn get_gloss_occurrence_order(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<(u32, u32)>, GlosserError> {
+        let query = "SELECT a.gloss_id, a.word_id FROM words a \
+        INNER JOIN course_x_text c ON (a.text_id = c.text_id AND c.course_id = $1) \
+        WHERE a.gloss_id IS NOT NULL AND a.type = $2 \
+        ORDER BY c.text_order, a.seq;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(WordType::Word as i32)
+            .map(|rec: PgRow| {
+                (
+                    u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                    u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
+                )
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_unarrowed_occurrences(
+        &mut self,
+        course_id: u32,
+        skip_units_through: u32,
+        tags: &TagFilter,
+    ) -> Result<Vec<AutoArrowRow>, GlosserError> {
+        let query = format!(
+            "SELECT a.word_id, a.word, a.text_id, a.gloss_id, g.lemma FROM words a \
+            INNER JOIN course_x_text c ON (a.text_id = c.text_id AND c.course_id = $1) \
+            INNER JOIN glosses g ON a.gloss_id = g.gloss_id \
+            LEFT JOIN arrowed_words d ON (d.course_id = $1 AND d.gloss_id = a.gloss_id) \
+            WHERE d.gloss_id IS NULL AND g.status > 0 AND a.type = $2 \
+            AND NOT (g.unit BETWEEN 1 AND $3){} \
+            ORDER BY c.text_order, a.seq;",
+            tags.sql("a.gloss_id")
+        );
+        sqlx::query(&query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(WordType::Word as i32)
+            .bind(i32::try_from(skip_units_through).unwrap())
+            .map(|rec: PgRow| AutoArrowRow {
+                gloss_id: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                lemma: rec.get("lemma"),
+                word_id: u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
+                word: rec.get("word"),
+                text_id: u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
     //word_id is unique across courses, so we do not need to use course_id except for where the word is arrowed
     //to do: we need to send back updated counts both for the new gloss_id and for the old gloss_id, if one was set
     //to do: can we limit what is sent back by the text being viewed?
@@ -510,12 +967,13 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {course_id}
             GROUP BY gloss_id
         )
-        SELECT B.gloss_id, B.lemma, B.pos, B.def, total_count, A.seq, A.word_id, \
+        SELECT B.gloss_id, B.lemma, B.pos, COALESCE(CG.def, B.def) AS def, total_count, A.seq, A.word_id, \
     D.word_id as arrowedID, E.seq AS arrowedseq, A.isflagged, G.text_order, F.text_order AS arrowed_text_order, \
     COUNT(A.gloss_id) OVER (PARTITION BY A.gloss_id ORDER BY G.text_order,A.seq ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)
         AS running_count
     FROM words A \
     LEFT JOIN glosses B ON A.gloss_id = B.gloss_id \
+    LEFT JOIN course_x_gloss CG ON (A.gloss_id = CG.gloss_id AND CG.course_id = {course_id}) \
     LEFT JOIN arrowed_words D ON (A.gloss_id = D.gloss_id AND D.course_id = {course_id}) \
     LEFT JOIN words E ON E.word_id = D.word_id \
     LEFT JOIN course_x_text F ON (E.text_id = F.text_id AND F.course_id = {course_id}) \
@@ -614,19 +1072,13 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         //arrow integer NOT NULL DEFAULT 0, flagged integer NOT NULL DEFAULT 0, updated timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
         //updatedUserAgent varchar (255) NOT NULL DEFAULT '', updatedIP varchar (255) NOT NULL DEFAULT '', updatedUser varchar (255) NOT NULL DEFAULT '', isFlagged integer NOT NULL DEFAULT 0, note varchar (1024) NOT NULL DEFAULT '')
 
-        let mut seq: u32 = 1;
-
         let query = "INSERT INTO words (seq, text_id, word, gloss_id, \
         type, updated, updatedUser, isFlagged, note) \
         VALUES ($1, $2, $3, $4, $5, $6, $7, 0, '');";
         let mut count = 0;
         let mut gloss_ids: HashSet<u32> = HashSet::new();
-        for w in words {
-            let gloss_id = if w.gloss_id.is_some() {
-                Some(i32::try_from(w.gloss_id.unwrap()).unwrap())
-            } else {
-                None
-            };
+        for (seq, w) in (1_u32..).zip(words.into_iter()) {
+            let gloss_id = w.gloss_id.map(|g| i32::try_from(g).unwrap());
             let res = sqlx::query(query)
                 .bind(i32::try_from(seq).unwrap())
                 .bind(text_id)
@@ -643,8 +1095,6 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
                 gloss_ids.insert(g_id);
             }
 
-            seq += 1;
-
             let affected_rows = res.rows_affected();
             if affected_rows != 1 {
                 return Err(GlosserError::UnknownError);
@@ -660,11 +1110,7 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .unwrap();
         //.map_err(map_sqlx_error)?;
 
-        let max_text_order: i32 = if max_text_order.0.is_some() {
-            max_text_order.0.unwrap() as i32
-        } else {
-            0
-        };
+        let max_text_order: i32 = max_text_order.0.unwrap_or(0);
 
         let query =
             "INSERT INTO course_x_text (course_id, text_id, text_order) VALUES ($1, $2, $3);";
@@ -692,25 +1138,22 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         Ok((count, text_id))
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn insert_gloss(
         &mut self,
         gloss: &str,
         pos: &str,
         def: &str,
-        stripped_lemma: &str,
         note: &str,
+        unit: u32,
+        parts: &GlossParts,
         info: &ConnectionInfo,
     ) -> Result<(i64, u64), GlosserError> {
         let query = "INSERT INTO glosses (unit, lemma, sortalpha, \
-        def, pos, note, updated, status, updatedUser) \
-        VALUES (0, $1, $2, $3, $4, $5, $6, 1, $7) RETURNING gloss_id;";
+        def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations, sortkey) \
+        VALUES ($12, $1, $2, $3, $4, $5, $6, $14, $7, $8, $9, $10, $11, $13) RETURNING gloss_id;";
 
-        //double check that diacritics are stripped and word is lowercased; doesn't handle pua here yet
-        let sl = stripped_lemma
-            .nfd()
-            .filter(|x| !unicode_normalization::char::is_combining_mark(*x))
-            .collect::<String>()
-            .to_lowercase();
+        let sl = make_sortalpha(gloss);
 
         let res = sqlx::query(query)
             .bind(gloss)
@@ -720,6 +1163,13 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .bind(note)
             .bind(info.timestamp)
             .bind(i32::try_from(info.user_id).unwrap())
+            .bind(&parts.principal_parts)
+            .bind(&parts.genitive)
+            .bind(&parts.gender)
+            .bind(&parts.terminations)
+            .bind(i32::try_from(unit).unwrap())
+            .bind(sort_key(gloss))
+            .bind(GlossStatus::Draft.value())
             .fetch_one(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
@@ -740,34 +1190,91 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         Ok((new_gloss_id as i64, 1))
     }
 
-    async fn update_log_trx(
+    async fn set_gloss_unit(
         &mut self,
-        update_type: UpdateType,
-        object_id: Option<i64>,
-        history_id: Option<i64>,
-        course_id: Option<i64>,
-        update_desc: &str,
+        gloss_id: u32,
+        unit: u32,
         info: &ConnectionInfo,
-    ) -> Result<(), GlosserError> {
-        let query = "INSERT INTO update_log \
-        (update_type, object_id, history_id, course_id, update_desc, updated, user_id, ip, user_agent) \
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);";
-        sqlx::query(query)
-            .bind(i32::try_from(update_type.value()).unwrap())
-            .bind(object_id)
-            .bind(history_id)
-            .bind(course_id)
-            .bind(update_desc)
-            .bind(info.timestamp)
-            .bind(i32::try_from(info.user_id).unwrap())
-            .bind(&info.ip_address)
-            .bind(&info.user_agent)
-            .execute(&mut *self.tx)
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT unit FROM glosses WHERE gloss_id = $1;";
+        let old_unit: u32 = sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("unit")).unwrap())
+            .fetch_one(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
+        if old_unit == unit {
+            return Ok(0);
+        }
 
-        Ok(())
-    }
+        let query = "INSERT INTO glosses_history \
+        (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1 RETURNING gloss_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+
+        self.update_log_trx(
+            UpdateType::SetGlossUnit,
+            Some(gloss_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Changed unit of gloss ({}) from {} to {}",
+                gloss_id, old_unit, unit
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query =
+            "UPDATE glosses SET unit = $1, updated = $2, updatedUser = $3 WHERE gloss_id = $4;";
+        let res = sqlx::query(query)
+            .bind(i32::try_from(unit).unwrap())
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn update_log_trx(
+        &mut self,
+        update_type: UpdateType,
+        object_id: Option<i64>,
+        history_id: Option<i64>,
+        course_id: Option<i64>,
+        update_desc: &str,
+        info: &ConnectionInfo,
+    ) -> Result<(), GlosserError> {
+        let query = "INSERT INTO update_log \
+        (update_type, object_id, history_id, course_id, update_desc, updated, user_id, ip, user_agent) \
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);";
+        sqlx::query(query)
+            .bind(i32::try_from(update_type.value()).unwrap())
+            .bind(object_id)
+            .bind(history_id)
+            .bind(course_id)
+            .bind(update_desc)
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .bind(&info.ip_address)
+            .bind(&info.user_agent)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(())
+    }
 
     async fn delete_gloss(
         &mut self,
@@ -783,19 +1290,34 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .map_err(map_sqlx_error)?;
 
         if count.0 == 0 {
+            //saved so a restore can put back the status it had
+            let query = "INSERT INTO glosses_history \
+            (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+            SELECT gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations \
+            FROM glosses WHERE gloss_id = $1 RETURNING gloss_history_id;";
+            let history_id: i64 = sqlx::query(query)
+                .bind(i32::try_from(gloss_id).unwrap())
+                .fetch_one(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .get::<i32, _>(0)
+                .into();
+
             self.update_log_trx(
                 UpdateType::DeleteGloss,
                 Some(gloss_id.into()),
-                Some(gloss_id.into()),
+                Some(history_id),
                 None,
                 format!("Deleted gloss ({})", gloss_id).as_str(),
                 info,
             )
             .await?;
 
-            let query = "UPDATE glosses SET status = 0 WHERE gloss_id = $1;";
+            let query = "UPDATE glosses SET status = 0, updated = $2, updatedUser = $3 WHERE gloss_id = $1;";
             let res = sqlx::query(query)
                 .bind(i32::try_from(gloss_id).unwrap())
+                .bind(info.timestamp)
+                .bind(i32::try_from(info.user_id).unwrap())
                 .execute(&mut *self.tx)
                 .await
                 .map_err(map_sqlx_error)?;
@@ -813,13 +1335,14 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         gloss: &str,
         pos: &str,
         def: &str,
-        stripped_gloss: &str,
         note: &str,
+        parts: &GlossParts,
         info: &ConnectionInfo,
     ) -> Result<u64, GlosserError> {
         let query = "INSERT INTO glosses_history \
-        (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser) \
-        SELECT * FROM glosses WHERE gloss_id = $1 RETURNING gloss_history_id;";
+        (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1 RETURNING gloss_history_id;";
         let history_id_row = sqlx::query(query)
             .bind(i32::try_from(gloss_id).unwrap())
             .fetch_one(&mut *self.tx)
@@ -846,22 +1369,22 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
 
         //CREATE TABLE IF NOT EXISTS update_log (update_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, update_type INTEGER REFERENCES update_types(update_type_id), object_id INTEGER, history_id INTEGER, course_id INTEGER, update_desc TEXT, comment TEXT, updated INTEGER NOT NULL, user_id INTEGER REFERENCES users(user_id), ip TEXT, user_agent TEXT );
 
-        //double check that diacritics are stripped and word is lowercased; doesn't handle pua here yet
-        let sl = stripped_gloss
-            .nfd()
-            .filter(|x| !unicode_normalization::char::is_combining_mark(*x))
-            .collect::<String>()
-            .to_lowercase();
+        let sl = make_sortalpha(gloss);
 
         let query = "UPDATE glosses SET \
         lemma = $1, \
         sortalpha = $2, \
+        sortkey = $13, \
         def = $3, \
         pos = $4, \
         note = $5, \
         updated = $6, \
-        updatedUser = $7 \
-        WHERE gloss_id = $8;";
+        updatedUser = $7, \
+        principal_parts = NULLIF(COALESCE($8, principal_parts), ''), \
+        genitive = NULLIF(COALESCE($9, genitive), ''), \
+        gender = NULLIF(COALESCE($10, gender), ''), \
+        terminations = NULLIF(COALESCE($11, terminations), '') \
+        WHERE gloss_id = $12;";
 
         let res = sqlx::query(query)
             .bind(gloss)
@@ -871,7 +1394,12 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .bind(note)
             .bind(info.timestamp)
             .bind(i32::try_from(info.user_id).unwrap())
+            .bind(&parts.principal_parts)
+            .bind(&parts.genitive)
+            .bind(&parts.gender)
+            .bind(&parts.terminations)
             .bind(i32::try_from(gloss_id).unwrap())
+            .bind(sort_key(gloss))
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
@@ -956,11 +1484,12 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         course_id: u32,
     ) -> Result<Vec<WordRow>, GlosserError> {
         let query = format!(
-            "SELECT a.word_id, a.word, a.type, b.lemma, b.def, b.sortalpha, b.unit, b.pos, d.word_id as arrowedid, \
-        b.gloss_id, a.seq, e.seq AS arrowedseq, \
+            "SELECT a.word_id, a.word, a.type, b.lemma, COALESCE(cg.def, b.def) AS def, NULLIF(COALESCE(cg.note, b.note), '') AS note, b.sortalpha, b.unit, b.pos, d.word_id as arrowedid, \
+        b.gloss_id, a.seq, e.seq AS arrowedseq, b.principal_parts, b.genitive, b.gender, b.terminations, \
         a.isFlagged, g.text_order, f.text_order AS arrowed_text_order, c.word_id as page_break, h.entry AS appcrit_entry \
         FROM words a \
         LEFT JOIN glosses b ON a.gloss_id = b.gloss_id \
+        LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {course_id}) \
         LEFT JOIN latex_page_breaks c ON a.word_id = c.word_id \
         LEFT JOIN arrowed_words d ON (a.gloss_id = d.gloss_id AND d.course_id = {course_id}) \
         LEFT JOIN words e ON e.word_id = d.word_id \
@@ -979,8 +1508,11 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
                 wordid: u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
                 word: rec.get("word"),
                 word_type: u8::try_from(rec.get::<i32, _>("type")).unwrap(),
-                lemma: rec.get("lemma"),
+                lemma: rec
+                    .get::<Option<String>, _>("lemma")
+                    .map(|l| gloss_parts_from_row(&rec).display_lemma(&l)),
                 def: rec.get("def"),
+                note: rec.get("note"),
                 unit: if rec.get::<Option<i32>, _>("unit").is_some() {
                     Some(u8::try_from(rec.get::<Option<i32>, _>("unit").unwrap()).unwrap())
                 } else {
@@ -1057,12 +1589,13 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {course_id}
             GROUP BY gloss_id
         )
-        SELECT a.word_id, a.word, a.type, b.lemma AS lemma, b.def, b.unit, b.pos, b.sortalpha, d.word_id as arrowedid, b.gloss_id, a.seq, e.seq AS arrowedseq,
+        SELECT a.word_id, a.word, a.type, b.lemma AS lemma, COALESCE(cg.def, b.def) AS def, NULLIF(COALESCE(cg.note, b.note), '') AS note, b.unit, b.pos, b.sortalpha, d.word_id as arrowedid, b.gloss_id, a.seq, e.seq AS arrowedseq,
         a.isFlagged, g.text_order, f.text_order AS arrowed_text_order, total_count, c.word_id as page_break,
         COUNT(a.gloss_id) OVER (PARTITION BY a.gloss_id ORDER BY a.seq ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)
         + COALESCE(running_basis, 0) AS running_count
         FROM words a
         LEFT JOIN glosses b ON a.gloss_id = b.gloss_id
+        LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {course_id})
         LEFT JOIN latex_page_breaks c ON a.word_id = c.word_id
         LEFT JOIN arrowed_words d ON (a.gloss_id = d.gloss_id AND d.course_id = {course_id})
         LEFT JOIN words e ON e.word_id = d.word_id
@@ -1081,6 +1614,7 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
                 word_type: u8::try_from(rec.get::<i32, _>("type")).unwrap(),
                 lemma: rec.get("lemma"),
                 def: rec.get("def"),
+                note: rec.get("note"),
                 unit: if rec.get::<Option<i32>, _>("unit").is_some() {
                     Some(u8::try_from(rec.get::<Option<i32>, _>("unit").unwrap()).unwrap())
                 } else {
@@ -1186,6 +1720,67 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         Ok(res.0)
     }
 
+    async fn update_text(
+        &mut self,
+        text_id: u32,
+        name: &str,
+        title: Option<&str>,
+        display: Option<bool>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT name, title FROM texts WHERE text_id = $1;";
+        let old: Option<(String, String)> = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| (rec.get("name"), rec.get("title")))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((old_name, old_title)) = old else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO texts_history (text_id, name, parent_id, display, title, updated, user_id) \
+        SELECT text_id, name, parent_id, display, title, $2, $3 FROM texts WHERE text_id = $1 RETURNING text_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+        self.update_log_trx(
+            UpdateType::EditText,
+            Some(text_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Edit text ({}) name from {} to {}, title from {} to {}",
+                text_id,
+                old_name,
+                name,
+                old_title,
+                title.unwrap_or(&old_title)
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query = "UPDATE texts SET name = $1, title = COALESCE($2, title), display = COALESCE($3, display) WHERE text_id = $4;";
+        let res = sqlx::query(query)
+            .bind(name)
+            .bind(title)
+            .bind(display.map(i32::from))
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
     // async fn update_counts_for_text_trx<'a, 'b>(
     //     tx: &'a mut sqlx::Transaction<'b, sqlx::Sqlite>,
     //     course_id: u32,
@@ -1367,103 +1962,108 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         text_id: u32,
         step: i32,
     ) -> Result<(), GlosserError> {
-        let text_id: i32 = i32::try_from(text_id).unwrap();
-        let course_id: i32 = i32::try_from(course_id).unwrap();
-        /*
-        //has children? move children with parent
-        let query = "SELECT a.text_id,b.text_order FROM texts a \
-                    INNER JOIN course_x_text b ON a.text_id=b.text_id \
-                    WHERE parent_id = ? ORDER BY b.text_order;";
-        let children: Vec<(i32,i32,)> = sqlx::query_as(query)
-            .bind(text_id)
-            .fetch_all(pool).await?;
-        println!("children: {:?}", children);
-        let num_to_move = 1 + children.len();
-        if !children.empty() {
-        }
-        //has parent? only move among siblings
-        let query = "SELECT parent_id FROM texts WHERE text_id = ?;";
-        let has_parent: (Option<i32>,) = sqlx::query_as(query)
-            .bind(text_id)
-            .fetch_one(pool).await?;
-        println!("parent: {:?}", has_parent);
-        containers
-            container_id
-            name
-        containers_x_text
-            container_id
-            text_id
-        container moving down: container and its children get + 1, following text gets - 1 or following container + children get -1
-        container moving up: container and its children get - 1, text above gets + num_children + 1
-        text where moving is child and moving up?
-        */
-
-        // get text order int
-        let query = "SELECT text_order FROM course_x_text WHERE course_id = $1 AND text_id = $2;";
-        let text_order: (i32,) = sqlx::query_as(query)
-            .bind(course_id)
-            .bind(text_id)
-            .fetch_one(&mut *self.tx)
-            .await
-            .map_err(map_sqlx_error)?;
+        let containers: Vec<u32> = self
+            .get_course_containers(course_id)
+            .await?
+            .into_iter()
+            .map(|c| c.0)
+            .collect();
+        let rows = self.get_course_text_tree(course_id).await?;
+        // no where to move: abort
+        let order =
+            reorder_texts(&rows, &containers, text_id, step).ok_or(GlosserError::UnknownError)?;
+        self.set_course_text_order(course_id, &order).await
+    }
 
-        // get number of texts
-        let query = "SELECT COUNT(*) FROM course_x_text WHERE course_id = $1;";
-        let text_count: (i64,) = sqlx::query_as(query)
-            .bind(course_id)
-            .fetch_one(&mut *self.tx)
+    async fn get_course_text_tree(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<CourseTextRow>, GlosserError> {
+        let query = "SELECT B.text_id, P.text_id AS parent_id, X.container_id \
+        FROM course_x_text B \
+        INNER JOIN texts A ON A.text_id = B.text_id \
+        LEFT JOIN course_x_text P ON (P.course_id = B.course_id AND P.text_id = B.parent_id) \
+        LEFT JOIN container_x_text X ON (X.course_id = B.course_id AND X.text_id = B.text_id) \
+        WHERE B.course_id = $1 \
+        ORDER BY B.text_order, B.text_id;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| CourseTextRow {
+                text_id: u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
+                parent_id: rec
+                    .get::<Option<i32>, _>("parent_id")
+                    .map(|p| u32::try_from(p).unwrap()),
+                container_id: rec
+                    .get::<Option<i32>, _>("container_id")
+                    .map(|p| u32::try_from(p).unwrap()),
+            })
+            .fetch_all(&mut *self.tx)
             .await
-            .unwrap();
-        //.map_err(map_sqlx_error)?;
+            .map_err(map_sqlx_error)
+    }
 
-        if step == 0
-            || (text_order.0 + step < 1 && step < 0)
-            || (text_order.0 + step > text_count.0.try_into().unwrap() && step > 0)
-        {
-            return Err(GlosserError::UnknownError); // no where to move: abort
-        } else if step > 0 {
-            //make room by moving other texts up/earlier in sequence
-            let query = "UPDATE course_x_text SET text_order = text_order - 1 \
-                WHERE text_order > $1 AND text_order < $2 + $3 + 1 AND course_id = $4;";
-            sqlx::query(query)
-                .bind(text_order.0)
-                .bind(text_order.0)
-                .bind(step)
-                .bind(course_id)
-                .execute(&mut *self.tx)
-                .await
-                .map_err(map_sqlx_error)?;
-        } else {
-            //make room by moving other texts down/later in sequence
-            let query = "UPDATE course_x_text SET text_order = text_order + 1 \
-                WHERE text_order < $1 AND text_order > $2 + $3 - 1 AND course_id = $4;";
+    async fn set_course_text_order(
+        &mut self,
+        course_id: u32,
+        text_ids: &[u32],
+    ) -> Result<(), GlosserError> {
+        let query =
+            "UPDATE course_x_text SET text_order = $1 WHERE course_id = $2 AND text_id = $3;";
+        for (i, text_id) in text_ids.iter().enumerate() {
             sqlx::query(query)
-                .bind(text_order.0)
-                .bind(text_order.0)
-                .bind(step) //step will be negative here
-                .bind(course_id)
+                .bind(i32::try_from(i + 1).unwrap())
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(*text_id).unwrap())
                 .execute(&mut *self.tx)
                 .await
                 .map_err(map_sqlx_error)?;
         }
-        //set new text order
-        let query = "UPDATE course_x_text SET text_order = text_order + $1 WHERE course_id = $2 AND text_id = $3;";
-        sqlx::query(query)
-            .bind(step)
-            .bind(course_id)
-            .bind(text_id)
+        Ok(())
+    }
+
+    async fn set_text_parent(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+        parent_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query =
+            "UPDATE course_x_text SET parent_id = $1 WHERE course_id = $2 AND text_id = $3;";
+        let rows = sqlx::query(query)
+            .bind(parent_id.map(|p| i32::try_from(p).unwrap()))
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(text_id).unwrap())
             .execute(&mut *self.tx)
             .await
-            .map_err(map_sqlx_error)?;
+            .map_err(map_sqlx_error)?
+            .rows_affected();
 
-        Ok(())
+        let desc = match parent_id {
+            Some(p) => format!("Nest text ({}) under text ({})", text_id, p),
+            None => format!("Unnest text ({})", text_id),
+        };
+        self.update_log_trx(
+            UpdateType::NestText,
+            Some(text_id.into()),
+            None,
+            Some(course_id.into()),
+            desc.as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
     }
 
     async fn get_texts_db(&mut self, course_id: u32) -> Result<Vec<AssignmentRow>, GlosserError> {
-        let query = "SELECT A.text_id, A.name, A.parent_id, B.course_id, C.name AS container \
+        let query = "SELECT A.text_id, A.name, X.container_id, B.course_id, C.name AS container, \
+        P.text_id AS course_parent_id \
         FROM texts A \
         INNER JOIN course_x_text B ON (A.text_id = B.text_id AND B.course_id = $1) \
-        LEFT JOIN containers C ON A.parent_id = C.container_id \
+        LEFT JOIN container_x_text X ON (X.course_id = B.course_id AND X.text_id = A.text_id) \
+        LEFT JOIN containers C ON X.container_id = C.container_id \
+        LEFT JOIN course_x_text P ON (P.course_id = B.course_id AND P.text_id = B.parent_id) \
         WHERE display != 0 \
         ORDER BY B.text_order, A.text_id;";
         let res: Result<Vec<AssignmentRow>, GlosserError> = sqlx::query(query)
@@ -1471,8 +2071,8 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .map(|rec: PgRow| AssignmentRow {
                 text_id: u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
                 text: rec.get("name"),
-                container_id: if rec.get::<Option<i32>, _>("parent_id").is_some() {
-                    Some(u32::try_from(rec.get::<Option<i32>, _>("parent_id").unwrap()).unwrap())
+                container_id: if rec.get::<Option<i32>, _>("container_id").is_some() {
+                    Some(u32::try_from(rec.get::<Option<i32>, _>("container_id").unwrap()).unwrap())
                 } else {
                     None
                 },
@@ -1482,6 +2082,9 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
                     None
                 },
                 container: rec.get("container"),
+                parent_id: rec
+                    .get::<Option<i32>, _>("course_parent_id")
+                    .map(|p| u32::try_from(p).unwrap()),
             })
             .fetch_all(&mut *self.tx)
             .await
@@ -1489,73 +2092,1461 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
 
         res
     }
-    /*
-    async fn _get_titles(pool: &SqlitePool) -> Result<Vec<(String,u32)>, GlosserError> {
-        let query = "SELECT id,title FROM titles ORDER BY title;";
-        let res: Result<Vec<(String,u32)>, GlosserError> = sqlx::query(query)
-        .map(|rec: PgRow| (rec.get("id"),rec.get("title")) )
-        .fetch_all(pool)
-        .await;
 
-        res
+    async fn get_course_containers(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<(u32, String)>, GlosserError> {
+        let query = "SELECT C.container_id, C.name \
+        FROM course_x_container X \
+        INNER JOIN containers C ON C.container_id = X.container_id \
+        WHERE X.course_id = $1 \
+        ORDER BY X.container_order, C.container_id;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    u32::try_from(rec.get::<i32, _>("container_id")).unwrap(),
+                    rec.get("name"),
+                )
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
     }
-    */
-    async fn get_text_id_for_word_id(&mut self, word_id: u32) -> Result<u32, GlosserError> {
-        let word_id = i32::try_from(word_id).unwrap();
 
-        let query = "SELECT text_id FROM words WHERE word_id = $1;";
-        let rec: (i32,) = sqlx::query_as(query)
-            .bind(word_id)
+    async fn insert_container(
+        &mut self,
+        course_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u32, GlosserError> {
+        let query = "INSERT INTO containers (name) VALUES ($1) RETURNING container_id;";
+        let container_id: i32 = sqlx::query(query)
+            .bind(name)
             .fetch_one(&mut *self.tx)
             .await
+            .map_err(map_sqlx_error)?
+            .get(0);
+        let query = "INSERT INTO course_x_container (course_id, container_id, container_order) \
+        VALUES ($1, $2, (SELECT COALESCE(MAX(container_order), 0) + 1 FROM course_x_container WHERE course_id = $1));";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(container_id)
+            .execute(&mut *self.tx)
+            .await
             .map_err(map_sqlx_error)?;
 
-        Ok(rec.0 as u32)
-    }
-    /*
-    async fn old_get_text_id_for_word_id(pool: &SqlitePool, word_id:u32) -> Result<u32, GlosserError> {
-    let query = "SELECT A.id FROM assignments A INNER JOIN words B ON A.start = B.word_id INNER JOIN words C ON A.end = C.word_id WHERE B.seq <= (SELECT seq FROM words WHERE word_id = ?) AND C.seq >= (SELECT seq FROM words WHERE word_id = ?) LIMIT 1;";
-
-    let rec: (u32,) = sqlx::query_as(query)
-    .bind(word_id)
-    .bind(word_id)
-    .fetch_one(pool)
-    .await?;
+        self.update_log_trx(
+            UpdateType::EditContainer,
+            Some(container_id.into()),
+            None,
+            Some(course_id.into()),
+            format!(
+                "Create container ({}) in course ({}): {}",
+                container_id, course_id, name
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
 
-    Ok(rec.0)
+        Ok(u32::try_from(container_id).unwrap())
     }
-    */
 
-    /*
-    async fn get_start_end(pool: &SqlitePool, text_id:u32) -> Result<(u32,u32), GlosserError> {
-    let query = "SELECT b.seq, c.seq FROM assignments a INNER JOIN words b ON a.start = b.word_id INNER JOIN words c ON a.end = c.word_id WHERE a.id = ?;";
+    async fn rename_container(
+        &mut self,
+        container_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "UPDATE containers SET name = $1 WHERE container_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(name)
+            .bind(i32::try_from(container_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
 
-    let rec: (u32,u32) = sqlx::query_as(query)
-    .bind(text_id)
-    .fetch_one(pool)
-    .await?;
+        self.update_log_trx(
+            UpdateType::EditContainer,
+            Some(container_id.into()),
+            None,
+            None,
+            format!("Rename container ({}) to {}", container_id, name).as_str(),
+            info,
+        )
+        .await?;
 
-    Ok(rec)
+        Ok(rows)
     }
-    */
-
-    async fn get_glossdb(&mut self, gloss_id: u32) -> Result<GlossEntry, GlosserError> {
-        let query = "SELECT gloss_id, lemma, pos, def, note FROM glosses WHERE gloss_id = $1;";
 
+    async fn delete_container(
+        &mut self,
+        course_id: u32,
+        container_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM container_x_text WHERE course_id = $1 AND container_id = $2;";
         sqlx::query(query)
-            .bind(i32::try_from(gloss_id).unwrap())
-            .map(|rec: PgRow| GlossEntry {
-                hqid: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
-                l: rec.get("lemma"),
-                pos: rec.get("pos"),
-                g: rec.get("def"),
-                n: rec.get("note"),
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(container_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM course_x_container WHERE course_id = $1 AND container_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(container_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "DELETE FROM containers WHERE container_id = $1 \
+        AND NOT EXISTS (SELECT 1 FROM course_x_container WHERE container_id = $1);";
+        sqlx::query(query)
+            .bind(i32::try_from(container_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::EditContainer,
+            Some(container_id.into()),
+            None,
+            Some(course_id.into()),
+            format!(
+                "Delete container ({}) from course ({})",
+                container_id, course_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_course_container_order(
+        &mut self,
+        course_id: u32,
+        container_ids: &[u32],
+    ) -> Result<(), GlosserError> {
+        let query = "UPDATE course_x_container SET container_order = $1 WHERE course_id = $2 AND container_id = $3;";
+        for (i, container_id) in container_ids.iter().enumerate() {
+            sqlx::query(query)
+                .bind(i32::try_from(i + 1).unwrap())
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(*container_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+        Ok(())
+    }
+
+    async fn set_text_container(
+        &mut self,
+        course_id: u32,
+        text_ids: &[u32],
+        container_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let mut rows = 0;
+        for text_id in text_ids {
+            let query = "DELETE FROM container_x_text WHERE course_id = $1 AND text_id = $2;";
+            sqlx::query(query)
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(*text_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+
+            if let Some(container_id) = container_id {
+                let query = "INSERT INTO container_x_text (course_id, text_id, container_id) VALUES ($1, $2, $3);";
+                rows += sqlx::query(query)
+                    .bind(i32::try_from(course_id).unwrap())
+                    .bind(i32::try_from(*text_id).unwrap())
+                    .bind(i32::try_from(container_id).unwrap())
+                    .execute(&mut *self.tx)
+                    .await
+                    .map_err(map_sqlx_error)?
+                    .rows_affected();
+            }
+        }
+
+        let text_list = text_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let desc = match container_id {
+            Some(c) => format!(
+                "Put texts ({}) in container ({}) in course ({})",
+                text_list, c, course_id
+            ),
+            None => format!(
+                "Take texts ({}) out of their container in course ({})",
+                text_list, course_id
+            ),
+        };
+        self.update_log_trx(
+            UpdateType::SetTextContainer,
+            text_ids.first().map(|t| (*t).into()),
+            None,
+            Some(course_id.into()),
+            desc.as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+    /*
+    async fn _get_titles(pool: &SqlitePool) -> Result<Vec<(String,u32)>, GlosserError> {
+        let query = "SELECT id,title FROM titles ORDER BY title;";
+        let res: Result<Vec<(String,u32)>, GlosserError> = sqlx::query(query)
+        .map(|rec: PgRow| (rec.get("id"),rec.get("title")) )
+        .fetch_all(pool)
+        .await;
+
+        res
+    }
+    */
+    async fn get_text_id_for_word_id(&mut self, word_id: u32) -> Result<u32, GlosserError> {
+        let word_id = i32::try_from(word_id).unwrap();
+
+        let query = "SELECT text_id FROM words WHERE word_id = $1;";
+        let rec: (i32,) = sqlx::query_as(query)
+            .bind(word_id)
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(rec.0 as u32)
+    }
+    /*
+    async fn old_get_text_id_for_word_id(pool: &SqlitePool, word_id:u32) -> Result<u32, GlosserError> {
+    let query = "SELECT A.id FROM assignments A INNER JOIN words B ON A.start = B.word_id INNER JOIN words C ON A.end = C.word_id WHERE B.seq <= (SELECT seq FROM words WHERE word_id = ?) AND C.seq >= (SELECT seq FROM words WHERE word_id = ?) LIMIT 1;";
+
+    let rec: (u32,) = sqlx::query_as(query)
+    .bind(word_id)
+    .bind(word_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(rec.0)
+    }
+    */
+
+    /*
+    async fn get_start_end(pool: &SqlitePool, text_id:u32) -> Result<(u32,u32), GlosserError> {
+    let query = "SELECT b.seq, c.seq FROM assignments a INNER JOIN words b ON a.start = b.word_id INNER JOIN words c ON a.end = c.word_id WHERE a.id = ?;";
+
+    let rec: (u32,u32) = sqlx::query_as(query)
+    .bind(text_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(rec)
+    }
+    */
+
+    async fn get_glossdb(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+    ) -> Result<GlossEntry, GlosserError> {
+        let query = "SELECT a.gloss_id, a.lemma, a.pos, a.def, a.note, a.status, a.unit, a.principal_parts, a.genitive, a.gender, a.terminations, \
+        b.def AS course_def, b.note AS course_note \
+        FROM glosses a \
+        LEFT JOIN course_x_gloss b ON (a.gloss_id = b.gloss_id AND b.course_id = $1) \
+        WHERE a.gloss_id = $2;";
+
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| GlossEntry {
+                hqid: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                l: rec.get("lemma"),
+                pos: rec.get("pos"),
+                g: rec.get("def"),
+                n: rec.get("note"),
+                parts: gloss_parts_from_row(&rec),
+                course_def: rec.get("course_def"),
+                course_note: rec.get("course_note"),
+                tags: vec![],
+                status: gloss_status_from_row(&rec),
+                unit: u32::try_from(rec.get::<i32, _>("unit")).unwrap(),
+            })
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn attach_text(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "INSERT INTO course_x_text (course_id, text_id, text_order) \
+        SELECT $1, text_id, (SELECT COALESCE(MAX(text_order), 0) + 1 FROM course_x_text WHERE course_id = $1) \
+        FROM texts WHERE text_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        if rows > 0 {
+            self.update_log_trx(
+                UpdateType::AttachText,
+                Some(text_id.into()),
+                None,
+                Some(course_id.into()),
+                format!("Attach text ({}) to course ({})", text_id, course_id).as_str(),
+                info,
+            )
+            .await?;
+        }
+
+        Ok(rows)
+    }
+
+    async fn detach_text(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM container_x_text WHERE course_id = $1 AND text_id = $2;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM course_x_text WHERE course_id = $1 AND text_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        self.update_log_trx(
+            UpdateType::DetachText,
+            Some(text_id.into()),
+            None,
+            Some(course_id.into()),
+            format!("Detach text ({}) from course ({})", text_id, course_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_text_arrows(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+    ) -> Result<Vec<(u32, u32)>, GlosserError> {
+        //H&Q arrows at the placeholder word_id 1 are not tied to a text
+        let query = "SELECT A.gloss_id, A.word_id \
+        FROM arrowed_words A \
+        INNER JOIN words W ON W.word_id = A.word_id \
+        WHERE A.course_id = $1 AND W.text_id = $2 AND A.word_id != 1 \
+        ORDER BY W.seq;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                    u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
+                )
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_text_word_ids(&mut self, text_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT word_id FROM words WHERE text_id = $1 ORDER BY seq;";
+        sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("word_id")).unwrap())
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_arrows_into_text(
+        &mut self,
+        text_id: u32,
+    ) -> Result<Vec<TextArrowRow>, GlosserError> {
+        let query = "SELECT A.course_id, A.gloss_id, A.word_id \
+        FROM arrowed_words A \
+        INNER JOIN words W ON W.word_id = A.word_id \
+        WHERE W.text_id = $1 AND A.word_id != 1 \
+        ORDER BY A.course_id, W.seq;";
+        sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| TextArrowRow {
+                course_id: u32::try_from(rec.get::<i32, _>("course_id")).unwrap(),
+                gloss_id: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                word_id: u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
+                new_word_id: None,
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn delete_text(
+        &mut self,
+        text_id: u32,
+        rearrowed: usize,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT name FROM texts WHERE text_id = $1;";
+        let name: Option<String> = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| rec.get("name"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some(name) = name else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO texts_history (text_id, name, parent_id, display, title, updated, user_id) \
+        SELECT text_id, name, parent_id, display, title, $2, $3 FROM texts WHERE text_id = $1 RETURNING text_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+        //rows which hang off the text's words
+        for query in [
+            "DELETE FROM latex_page_breaks WHERE word_id IN (SELECT word_id FROM words WHERE text_id = $1);",
+            "DELETE FROM appcrit WHERE word_id IN (SELECT word_id FROM words WHERE text_id = $1);",
+            "DELETE FROM arrowed_words WHERE word_id IN (SELECT word_id FROM words WHERE text_id = $1);",
+        ] {
+            sqlx::query(query)
+                .bind(i32::try_from(text_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        //the words are archived to words_history
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE text_id = $1;";
+        let words = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        for query in [
+            "DELETE FROM words WHERE text_id = $1;",
+            "DELETE FROM container_x_text WHERE text_id = $1;",
+            "DELETE FROM course_x_text WHERE text_id = $1;",
+            "UPDATE course_x_text SET parent_id = NULL WHERE parent_id = $1;",
+            "UPDATE texts SET parent_id = NULL WHERE parent_id = $1;",
+        ] {
+            sqlx::query(query)
+                .bind(i32::try_from(text_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        let query = "DELETE FROM texts WHERE text_id = $1;";
+        let rows = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        self.update_log_trx(
+            UpdateType::DeleteText,
+            Some(text_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Delete text ({}) {} and archive its {} words{}",
+                text_id,
+                name,
+                words,
+                if rearrowed > 0 {
+                    format!(", re-arrowed {} glosses", rearrowed)
+                } else {
+                    String::from("")
+                }
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_text_course_ids(&mut self, text_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT course_id FROM course_x_text WHERE text_id = $1 ORDER BY course_id;";
+        sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("course_id")).unwrap())
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn split_text(
+        &mut self,
+        text_id: u32,
+        word_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<Option<u32>, GlosserError> {
+        let query = "SELECT seq FROM words WHERE word_id = $1 AND text_id = $2;";
+        let start: Option<i32> = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| rec.get("seq"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some(start) = start else {
+            return Ok(None);
+        };
+
+        let query = "INSERT INTO texts (name, parent_id, display, title) \
+        SELECT $1, parent_id, display, title FROM texts WHERE text_id = $2 RETURNING text_id;";
+        let new_text_id: i32 = sqlx::query(query)
+            .bind(name)
+            .bind(i32::try_from(text_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get(0);
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE text_id = $1 AND seq >= $2;";
+        sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(start)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query =
+            "UPDATE words SET text_id = $1, seq = seq - $2 + 1 WHERE text_id = $3 AND seq >= $2;";
+        let moved = sqlx::query(query)
+            .bind(new_text_id)
+            .bind(start)
+            .bind(i32::try_from(text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        //the new text takes the old one's place in each course and container, to be reordered
+        for query in [
+            "INSERT INTO course_x_text (course_id, text_id, text_order, parent_id) \
+            SELECT course_id, $1, text_order, parent_id FROM course_x_text WHERE text_id = $2;",
+            "INSERT INTO container_x_text (course_id, text_id, container_id) \
+            SELECT course_id, $1, container_id FROM container_x_text WHERE text_id = $2;",
+        ] {
+            sqlx::query(query)
+                .bind(new_text_id)
+                .bind(i32::try_from(text_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.update_log_trx(
+            UpdateType::SplitText,
+            Some(text_id.into()),
+            None,
+            None,
+            format!(
+                "Split text ({}) at word ({}), moving {} words to new text ({})",
+                text_id, word_id, moved, new_text_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(Some(u32::try_from(new_text_id).unwrap()))
+    }
+
+    async fn merge_texts(
+        &mut self,
+        text_id: u32,
+        next_text_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT COALESCE(MAX(seq), 0) AS last_seq FROM words WHERE text_id = $1;";
+        let last_seq: i32 = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .map(|rec: PgRow| rec.get("last_seq"))
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "INSERT INTO texts_history (text_id, name, parent_id, display, title, updated, user_id) \
+        SELECT text_id, name, parent_id, display, title, $2, $3 FROM texts WHERE text_id = $1 RETURNING text_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(next_text_id).unwrap())
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE text_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(next_text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "UPDATE words SET text_id = $1, seq = seq + $2 WHERE text_id = $3;";
+        let moved = sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(last_seq)
+            .bind(i32::try_from(next_text_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        for query in [
+            "DELETE FROM container_x_text WHERE text_id = $1;",
+            "DELETE FROM course_x_text WHERE text_id = $1;",
+            "UPDATE course_x_text SET parent_id = NULL WHERE parent_id = $1;",
+            "UPDATE texts SET parent_id = NULL WHERE parent_id = $1;",
+            "DELETE FROM texts WHERE text_id = $1;",
+        ] {
+            sqlx::query(query)
+                .bind(i32::try_from(next_text_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.update_log_trx(
+            UpdateType::MergeTexts,
+            Some(text_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Merge text ({}) into text ({}), moving {} words",
+                next_text_id, text_id, moved
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(moved)
+    }
+
+    async fn set_course_gloss(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+        def: Option<&str>,
+        note: Option<&str>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let res = if def.is_none() && note.is_none() {
+            let query = "DELETE FROM course_x_gloss WHERE course_id = $1 AND gloss_id = $2;";
+            sqlx::query(query)
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(gloss_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+        } else {
+            let query = "INSERT INTO course_x_gloss (course_id, gloss_id, def, note, updated, user_id) \
+            VALUES ($1, $2, $3, $4, $5, $6) \
+            ON CONFLICT (course_id, gloss_id) DO UPDATE SET def = $3, note = $4, updated = $5, user_id = $6;";
+            sqlx::query(query)
+                .bind(i32::try_from(course_id).unwrap())
+                .bind(i32::try_from(gloss_id).unwrap())
+                .bind(def)
+                .bind(note)
+                .bind(info.timestamp)
+                .bind(i32::try_from(info.user_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+        };
+
+        self.update_log_trx(
+            UpdateType::EditCourseGloss,
+            Some(gloss_id.into()),
+            None,
+            Some(course_id.into()),
+            format!(
+                "{} for gloss ({}) in course ({})",
+                match (def, note) {
+                    (Some(_), Some(_)) => "Set course definition and note",
+                    (Some(_), None) => "Set course definition",
+                    (None, Some(_)) => "Set course note",
+                    (None, None) => "Removed course definition and note",
+                },
+                gloss_id,
+                course_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_tags(&mut self) -> Result<Vec<Tag>, GlosserError> {
+        let query = "SELECT tag_id, name FROM tags ORDER BY name;";
+        sqlx::query(query)
+            .map(|rec: PgRow| Tag {
+                tag_id: u32::try_from(rec.get::<i32, _>("tag_id")).unwrap(),
+                name: rec.get("name"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn insert_tag(&mut self, name: &str, info: &ConnectionInfo) -> Result<i64, GlosserError> {
+        let query = "INSERT INTO tags (name) VALUES ($1) RETURNING tag_id;";
+        let tag_id: i64 = sqlx::query(query)
+            .bind(name)
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+
+        self.update_log_trx(
+            UpdateType::EditTag,
+            Some(tag_id),
+            None,
+            None,
+            format!("Added tag ({}) {}", tag_id, name).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(tag_id)
+    }
+
+    async fn update_tag(
+        &mut self,
+        tag_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "UPDATE tags SET name = $1 WHERE tag_id = $2;";
+        let res = sqlx::query(query)
+            .bind(name)
+            .bind(i32::try_from(tag_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::EditTag,
+            Some(tag_id.into()),
+            None,
+            None,
+            format!("Renamed tag ({}) to {}", tag_id, name).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn delete_tag(
+        &mut self,
+        tag_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM gloss_x_tag WHERE tag_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(tag_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM tags WHERE tag_id = $1;";
+        let res = sqlx::query(query)
+            .bind(i32::try_from(tag_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::EditTag,
+            Some(tag_id.into()),
+            None,
+            None,
+            format!("Deleted tag ({})", tag_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn tag_gloss(
+        &mut self,
+        gloss_id: u32,
+        tag_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query =
+            "INSERT INTO gloss_x_tag (gloss_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING;";
+        let res = sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .bind(i32::try_from(tag_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::TagGloss,
+            Some(gloss_id.into()),
+            None,
+            None,
+            format!("Added tag ({}) to gloss ({})", tag_id, gloss_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn untag_gloss(
+        &mut self,
+        gloss_id: u32,
+        tag_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM gloss_x_tag WHERE gloss_id = $1 AND tag_id = $2;";
+        let res = sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .bind(i32::try_from(tag_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::TagGloss,
+            Some(gloss_id.into()),
+            None,
+            None,
+            format!("Removed tag ({}) from gloss ({})", tag_id, gloss_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_gloss_tags(&mut self, gloss_id: u32) -> Result<Vec<Tag>, GlosserError> {
+        let query = "SELECT b.tag_id, b.name FROM gloss_x_tag a \
+        INNER JOIN tags b ON a.tag_id = b.tag_id \
+        WHERE a.gloss_id = $1 ORDER BY b.name;";
+        sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| Tag {
+                tag_id: u32::try_from(rec.get::<i32, _>("tag_id")).unwrap(),
+                name: rec.get("name"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_tagged_gloss_ids(&mut self, tag_ids: &[u32]) -> Result<Vec<u32>, GlosserError> {
+        if tag_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let query = format!(
+            "SELECT DISTINCT gloss_id FROM gloss_x_tag WHERE tag_id IN ({});",
+            tag_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        sqlx::query(&query)
+            .map(|rec: PgRow| u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap())
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn set_gloss_status(
+        &mut self,
+        gloss_id: u32,
+        status: GlossStatus,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT status FROM glosses WHERE gloss_id = $1;";
+        let old_status = sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| gloss_status_from_row(&rec))
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        if old_status == status {
+            return Ok(0);
+        }
+
+        let query = "INSERT INTO glosses_history \
+        (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1 RETURNING gloss_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+
+        self.update_log_trx(
+            UpdateType::SetGlossStatus,
+            Some(gloss_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Changed gloss ({}) from {} to {}",
+                gloss_id,
+                old_status.name(),
+                status.name()
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query =
+            "UPDATE glosses SET status = $1, updated = $2, updatedUser = $3 WHERE gloss_id = $4;";
+        let res = sqlx::query(query)
+            .bind(status.value())
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .bind(i32::try_from(gloss_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_glosses_by_status(
+        &mut self,
+        status: GlossStatus,
+    ) -> Result<Vec<GlossReviewRow>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, pos, def, status FROM glosses \
+        WHERE status = $1 ORDER BY sortalpha COLLATE \"C\", sortkey COLLATE \"C\";";
+        sqlx::query(query)
+            .bind(status.value())
+            .map(|rec: PgRow| gloss_review_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_unapproved_glosses(
+        &mut self,
+        text_ids: &[u32],
+    ) -> Result<Vec<GlossReviewRow>, GlosserError> {
+        if text_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let query = format!(
+            "SELECT DISTINCT b.gloss_id, b.lemma, b.pos, b.def, b.status, b.sortalpha, b.sortkey FROM words a \
+            INNER JOIN glosses b ON a.gloss_id = b.gloss_id \
+            WHERE a.text_id IN ({}) AND b.status != $1 ORDER BY b.sortalpha COLLATE \"C\", b.sortkey COLLATE \"C\";",
+            text_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        sqlx::query(&query)
+            .bind(GlossStatus::Approved.value())
+            .map(|rec: PgRow| gloss_review_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_text_gloss_counts(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<TextGlossCount>, GlosserError> {
+        let query = "SELECT B.text_id, T.name, B.text_order, A.gloss_id, G.unit, COUNT(A.word_id) AS tokens, \
+        F.text_order AS arrowed_text_order, COALESCE(D.word_id = 1, FALSE) AS arrowed_hq \
+        FROM course_x_text B \
+        INNER JOIN texts T ON B.text_id = T.text_id \
+        LEFT JOIN words A ON A.text_id = B.text_id AND A.type = 0 \
+        LEFT JOIN glosses G ON A.gloss_id = G.gloss_id \
+        LEFT JOIN arrowed_words D ON A.gloss_id = D.gloss_id AND D.course_id = $1 \
+        LEFT JOIN words E ON E.word_id = D.word_id \
+        LEFT JOIN course_x_text F ON E.text_id = F.text_id AND F.course_id = $1 \
+        WHERE B.course_id = $1 \
+        GROUP BY B.text_id, T.name, B.text_order, A.gloss_id, G.unit, F.text_order, D.word_id \
+        ORDER BY B.text_order, A.gloss_id;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .map(|rec: PgRow| TextGlossCount {
+                text_id: u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
+                text_name: rec.get("name"),
+                text_order: u32::try_from(rec.get::<i32, _>("text_order")).unwrap(),
+                gloss_id: rec
+                    .get::<Option<i32>, _>("gloss_id")
+                    .map(|v| u32::try_from(v).unwrap()),
+                unit: rec
+                    .get::<Option<i32>, _>("unit")
+                    .map(|v| u32::try_from(v).unwrap()),
+                tokens: u32::try_from(rec.get::<i64, _>("tokens")).unwrap(),
+                arrowed_text_order: rec
+                    .get::<Option<i32>, _>("arrowed_text_order")
+                    .map(|v| u32::try_from(v).unwrap()),
+                arrowed_hq: rec.get("arrowed_hq"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_glossary(
+        &mut self,
+        course_id: Option<u32>,
+    ) -> Result<Vec<GlossaryRow>, GlosserError> {
+        let (course_join, course_filter) = if course_id.is_some() {
+            (
+                "LEFT JOIN course_x_gloss cg ON (g.gloss_id = cg.gloss_id AND cg.course_id = $1)",
+                "AND g.gloss_id IN (SELECT a.gloss_id FROM words a \
+                INNER JOIN course_x_text b ON a.text_id = b.text_id WHERE b.course_id = $1)",
+            )
+        } else {
+            //no course, so the join matches nothing and the glosses' own def and note are used
+            (
+                "LEFT JOIN course_x_gloss cg ON (g.gloss_id = cg.gloss_id AND cg.course_id IS NULL)",
+                "",
+            )
+        };
+        let query = format!(
+            "SELECT g.gloss_id, g.lemma, g.sortalpha, g.pos, COALESCE(cg.def, g.def) AS def, COALESCE(cg.note, g.note) AS note, \
+            g.unit, g.status, g.principal_parts, g.genitive, g.gender, g.terminations \
+            FROM glosses g {} WHERE g.status > 0 {} ORDER BY g.sortalpha COLLATE \"C\", g.sortkey COLLATE \"C\", g.gloss_id;",
+            course_join, course_filter
+        );
+        let mut sql = sqlx::query(&query);
+        if let Some(course_id) = course_id {
+            sql = sql.bind(i32::try_from(course_id).unwrap());
+        }
+        sql.map(|rec: PgRow| glossary_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_deleted_glosses(&mut self) -> Result<Vec<DeletedGlossRow>, GlosserError> {
+        let query = "SELECT a.gloss_id, a.lemma, a.pos, a.def, b.updated AS deleted_at, c.initials AS deleted_by \
+        FROM glosses a \
+        LEFT JOIN update_log b ON b.update_id = \
+            (SELECT MAX(update_id) FROM update_log WHERE update_type = $1 AND object_id = a.gloss_id) \
+        LEFT JOIN users c ON b.user_id = c.user_id \
+        WHERE a.status = 0 ORDER BY a.sortalpha COLLATE \"C\", a.sortkey COLLATE \"C\";";
+        sqlx::query(query)
+            .bind(i32::try_from(UpdateType::DeleteGloss.value()).unwrap())
+            .map(|rec: PgRow| DeletedGlossRow {
+                gloss_id: u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                lemma: rec.get("lemma"),
+                pos: rec.get("pos"),
+                def: rec.get("def"),
+                deleted_by: rec.get("deleted_by"),
+                deleted_at: rec.get::<Option<i32>, _>("deleted_at").map(i64::from),
             })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_status_before_delete(
+        &mut self,
+        gloss_id: u32,
+    ) -> Result<Option<GlossStatus>, GlosserError> {
+        let query = "SELECT status FROM glosses_history WHERE gloss_id = $1 AND status > 0 \
+        ORDER BY gloss_history_id DESC LIMIT 1;";
+        sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| gloss_status_from_row(&rec))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn replace_gloss(
+        &mut self,
+        gloss_id: u32,
+        replacement_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<(u64, u64, u64, u64), GlosserError> {
+        let gloss_id_i32 = i32::try_from(gloss_id).unwrap();
+        let replacement = replacement_id.map(|r| i32::try_from(r).unwrap());
+        let query = "INSERT INTO arrowed_words_history (course_id, gloss_id, word_id, updated, user_id, comment) \
+        SELECT course_id, gloss_id, word_id, updated, user_id, comment FROM arrowed_words WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id_i32)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        //an arrow moves only if the replacement is not already arrowed in that course
+        let mut arrows_moved = 0;
+        if replacement.is_some() {
+            let query = "UPDATE arrowed_words SET gloss_id = $2, updated = $3, user_id = $4 \
+            WHERE gloss_id = $1 AND course_id NOT IN (SELECT course_id FROM arrowed_words WHERE gloss_id = $2);";
+            arrows_moved = sqlx::query(query)
+                .bind(gloss_id_i32)
+                .bind(replacement)
+                .bind(info.timestamp)
+                .bind(i32::try_from(info.user_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected();
+        }
+        let query = "DELETE FROM arrowed_words WHERE gloss_id = $1;";
+        let arrows_removed = sqlx::query(query)
+            .bind(gloss_id_i32)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id_i32)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query =
+            "UPDATE words SET gloss_id = $2, updated = $3, updatedUser = $4 WHERE gloss_id = $1;";
+        let words = sqlx::query(query)
+            .bind(gloss_id_i32)
+            .bind(replacement)
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let forms = if replacement.is_some() {
+            let query = "UPDATE lemmatizer SET gloss_id = $2 WHERE gloss_id = $1;";
+            sqlx::query(query)
+                .bind(gloss_id_i32)
+                .bind(replacement)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected()
+        } else {
+            let query = "DELETE FROM lemmatizer WHERE gloss_id = $1;";
+            sqlx::query(query)
+                .bind(gloss_id_i32)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected()
+        };
+
+        let query = "INSERT INTO glosses_history \
+        (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1 RETURNING gloss_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(gloss_id_i32)
             .fetch_one(&mut *self.tx)
             .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+
+        let query =
+            "UPDATE glosses SET status = 0, updated = $2, updatedUser = $3 WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id_i32)
+            .bind(info.timestamp)
+            .bind(i32::try_from(info.user_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let target = match replacement_id {
+            Some(r) => format!("gloss ({})", r),
+            None => String::from("nothing"),
+        };
+        self.update_log_trx(
+            UpdateType::DeleteGloss,
+            Some(gloss_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Deleted gloss ({}), replacing it with {}: {} words, {} lemmatizer forms, {} arrows moved, {} arrows removed",
+                gloss_id, target, words, forms, arrows_moved, arrows_removed
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok((words, forms, arrows_moved, arrows_removed))
+    }
+
+    async fn purge_glosses(&mut self, info: &ConnectionInfo) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT gloss_id, lemma FROM glosses a WHERE status = 0 \
+        AND NOT EXISTS (SELECT 1 FROM words WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM words_history WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM arrowed_words WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM arrowed_words_history WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM glosses_history h WHERE h.gloss_id = a.gloss_id \
+            AND h.gloss_history_id NOT IN (SELECT history_id FROM update_log \
+            WHERE update_type = $1 AND object_id = a.gloss_id AND history_id IS NOT NULL)) \
+        ORDER BY gloss_id;";
+        let glosses: Vec<(u32, String)> = sqlx::query(query)
+            .bind(i32::try_from(UpdateType::DeleteGloss.value()).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                    rec.get("lemma"),
+                )
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        for (gloss_id, lemma) in &glosses {
+            for query in [
+                "DELETE FROM gloss_x_tag WHERE gloss_id = $1;",
+                "DELETE FROM course_x_gloss WHERE gloss_id = $1;",
+                "DELETE FROM course_known_glosses WHERE gloss_id = $1;",
+                "DELETE FROM lemmatizer WHERE gloss_id = $1;",
+                "DELETE FROM glosses_history WHERE gloss_id = $1;",
+                "DELETE FROM glosses WHERE gloss_id = $1;",
+            ] {
+                sqlx::query(query)
+                    .bind(i32::try_from(*gloss_id).unwrap())
+                    .execute(&mut *self.tx)
+                    .await
+                    .map_err(map_sqlx_error)?;
+            }
+
+            self.update_log_trx(
+                UpdateType::PurgeGloss,
+                Some((*gloss_id).into()),
+                None,
+                None,
+                format!("Purged gloss ({}) {}", gloss_id, lemma).as_str(),
+                info,
+            )
+            .await?;
+        }
+
+        Ok(glosses.into_iter().map(|g| g.0).collect())
+    }
+
+    async fn get_sortalphas(&mut self) -> Result<Vec<(u32, String, String, String)>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, sortalpha, sortkey FROM glosses ORDER BY gloss_id;";
+        sqlx::query(query)
+            .map(|rec: PgRow| {
+                (
+                    u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                    rec.get("lemma"),
+                    rec.get("sortalpha"),
+                    rec.get("sortkey"),
+                )
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn set_sortalpha(
+        &mut self,
+        gloss_id: u32,
+        sortalpha: &str,
+        sortkey: &str,
+    ) -> Result<u64, GlosserError> {
+        let query = "UPDATE glosses SET sortalpha = $1, sortkey = $3 WHERE gloss_id = $2;";
+        let res = sqlx::query(query)
+            .bind(sortalpha)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .bind(sortkey)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(res.rows_affected())
+    }
+
+    async fn get_glossary_row(
+        &mut self,
+        gloss_id: u32,
+    ) -> Result<Option<GlossaryRow>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, sortalpha, pos, def, note, unit, status, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .map(|rec: PgRow| glossary_row(&rec))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn find_glossary_rows(
+        &mut self,
+        lemma: &str,
+        sortalpha: &str,
+    ) -> Result<Vec<GlossaryRow>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, sortalpha, pos, def, note, unit, status, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE lemma = $1 AND sortalpha = $2 AND status > 0;";
+        sqlx::query(query)
+            .bind(lemma)
+            .bind(sortalpha)
+            .map(|rec: PgRow| glossary_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
             .map_err(map_sqlx_error)
     }
 
+    async fn insert_glossary_row(
+        &mut self,
+        row: &GlossaryRow,
+        info: &ConnectionInfo,
+    ) -> Result<u32, GlosserError> {
+        let query = "INSERT INTO glosses (unit, lemma, sortalpha, \
+        def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations, sortkey) \
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) RETURNING gloss_id;";
+        let gloss_id = sqlx::query(query)
+            .bind(i32::try_from(row.unit.unwrap_or(0)).unwrap())
+            .bind(&row.lemma)
+            .bind(&row.sortalpha)
+            .bind(&row.def)
+            .bind(&row.pos)
+            .bind(&row.note)
+            .bind(info.timestamp)
+            .bind(row.status.unwrap_or(GlossStatus::Draft).value())
+            .bind(i32::try_from(info.user_id).unwrap())
+            .bind(&row.principal_parts)
+            .bind(&row.genitive)
+            .bind(&row.gender)
+            .bind(&row.terminations)
+            .bind(sort_key(&row.lemma))
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0);
+
+        Ok(u32::try_from(gloss_id).unwrap())
+    }
+
+    async fn update_glossary_row(
+        &mut self,
+        gloss_id: u32,
+        row: &GlossaryRow,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "INSERT INTO glosses_history \
+        (gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "UPDATE glosses SET \
+        unit = $1, \
+        lemma = $2, \
+        sortalpha = $3, \
+        sortkey = $15, \
+        def = $4, \
+        pos = $5, \
+        note = $6, \
+        updated = $7, \
+        status = $8, \
+        updatedUser = $9, \
+        principal_parts = $10, \
+        genitive = $11, \
+        gender = $12, \
+        terminations = $13 \
+        WHERE gloss_id = $14;";
+        let res = sqlx::query(query)
+            .bind(i32::try_from(row.unit.unwrap_or(0)).unwrap())
+            .bind(&row.lemma)
+            .bind(&row.sortalpha)
+            .bind(&row.def)
+            .bind(&row.pos)
+            .bind(&row.note)
+            .bind(info.timestamp)
+            .bind(row.status.unwrap_or(GlossStatus::Approved).value())
+            .bind(i32::try_from(info.user_id).unwrap())
+            .bind(&row.principal_parts)
+            .bind(&row.genitive)
+            .bind(&row.gender)
+            .bind(&row.terminations)
+            .bind(i32::try_from(gloss_id).unwrap())
+            .bind(sort_key(&row.lemma))
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
     //SELECT c.name, a.word_id, a.word, d.word_id as arrowed FROM words a INNER JOIN course_x_text b ON (a.text = b.text_id AND b.course_id = 1) INNER JOIN texts c ON a.text = c.text_id LEFT JOIN arrowed_words d ON (d.course_id=1 AND d.gloss_id=564 AND d.word_id = a.word_id) WHERE a.gloss_id = 564 ORDER BY b.text_order, a.seq LIMIT 20000;
 
     async fn get_gloss_occurrences(
@@ -1666,15 +3657,25 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         page: i32,
         limit: u32,
         course_id: u32,
+        regex: Option<&GlossRegex>,
+        tags: &TagFilter,
     ) -> Result<Vec<(String, u32, String, u32)>, GlosserError> {
+        let regex_filter = match regex {
+            Some(r) => format!("AND a.{} ~ $1", r.column()),
+            None => String::from(""),
+        };
         let query = format!("WITH gloss_total AS (
             SELECT gloss_id, COUNT(gloss_id) AS total_count
             FROM words a2
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {}
             GROUP BY gloss_id
         )
-        SELECT a.gloss_id, a.lemma, a.def, COALESCE(b.total_count, 0) AS total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id WHERE a.sortalpha < '{}' AND status > 0 AND pos != 'gloss' ORDER BY a.sortalpha DESC LIMIT {} OFFSET {};", course_id, searchprefix, limit, -page * limit as i32);
-        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sqlx::query(&query)
+        SELECT a.gloss_id, a.lemma, COALESCE(cg.def, a.def) AS def, COALESCE(b.total_count, 0) AS total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {}) WHERE a.sortalpha COLLATE \"C\" < '{}' AND status > 0 AND pos != 'gloss' {}{} ORDER BY a.sortalpha COLLATE \"C\" DESC, a.sortkey COLLATE \"C\" DESC, a.gloss_id DESC LIMIT {} OFFSET {};", course_id, course_id, searchprefix, regex_filter, tags.sql("a.gloss_id"), limit, -page * limit as i32);
+        let mut sql = sqlx::query(&query);
+        if let Some(r) = regex {
+            sql = sql.bind(&r.pattern);
+        }
+        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sql
             .map(|rec: PgRow| {
                 (
                     rec.get("lemma"),
@@ -1696,16 +3697,26 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         page: i32,
         limit: u32,
         course_id: u32,
+        regex: Option<&GlossRegex>,
+        tags: &TagFilter,
     ) -> Result<Vec<(String, u32, String, u32)>, GlosserError> {
+        let regex_filter = match regex {
+            Some(r) => format!("AND a.{} ~ $1", r.column()),
+            None => String::from(""),
+        };
         let query = format!("WITH gloss_total AS (
             SELECT gloss_id, COUNT(gloss_id) AS total_count
             FROM words a2
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {}
             GROUP BY gloss_id
         )
-        SELECT a.gloss_id, a.lemma, a.def, COALESCE(b.total_count, 0) AS total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id WHERE a.sortalpha >= '{}' AND status > 0 AND pos != 'gloss' ORDER BY a.sortalpha LIMIT {} OFFSET {};",
-        course_id, searchprefix, limit, page * limit as i32);
-        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sqlx::query(&query)
+        SELECT a.gloss_id, a.lemma, COALESCE(cg.def, a.def) AS def, COALESCE(b.total_count, 0) AS total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {}) WHERE a.sortalpha COLLATE \"C\" >= '{}' AND status > 0 AND pos != 'gloss' {}{} ORDER BY a.sortalpha COLLATE \"C\", a.sortkey COLLATE \"C\", a.gloss_id LIMIT {} OFFSET {};",
+        course_id, course_id, searchprefix, regex_filter, tags.sql("a.gloss_id"), limit, page * limit as i32);
+        let mut sql = sqlx::query(&query);
+        if let Some(r) = regex {
+            sql = sql.bind(&r.pattern);
+        }
+        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sql
             .map(|rec: PgRow| {
                 (
                     rec.get("lemma"),
@@ -1775,32 +3786,300 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
         before_word_id: u32,
         word_type: u32,
         word: &str,
-    ) -> Result<i64, GlosserError> {
-        let query = r#"UPDATE words set seq = seq + 1 WHERE text_id > 269 AND text_id < 296 AND seq >= (SELECT seq FROM words WHERE word_id = $1);"#;
-        let _res = sqlx::query(query)
+        info: &ConnectionInfo,
+    ) -> Result<Option<u32>, GlosserError> {
+        let query = "SELECT seq, text_id FROM words WHERE word_id = $1;";
+        let before: Option<(i32, u32)> = sqlx::query(query)
             .bind(i32::try_from(before_word_id).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    rec.get("seq"),
+                    u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
+                )
+            })
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((seq, text_id)) = before else {
+            return Ok(None);
+        };
+
+        let query = "UPDATE words SET seq = seq + 1 WHERE text_id = $1 AND seq >= $2;";
+        sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(seq)
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
 
         //do not insert value for updated so it gets default timestamp, inserting null to it does not set the timestamp
-        let query2 = r#"INSERT INTO words (word_id, seq, text_id, word, gloss_id, type, updatedUser, isFlagged, note)
-            VALUES (NULL,
-                (SELECT seq - 1 FROM words WHERE word_id = $1),
-                (SELECT text_id FROM words WHERE word_id = $2),
-                $3, NULL, $4, '', 0, '');"#;
-        let word_id_row = sqlx::query(query2)
-            .bind(i32::try_from(before_word_id).unwrap())
-            .bind(i32::try_from(before_word_id).unwrap())
-            .bind(word) //''
-            .bind(i32::try_from(word_type).unwrap()) //6
+        let query = "INSERT INTO words (seq, text_id, word, gloss_id, type, updatedUser, isFlagged, note) \
+        VALUES ($1, $2, $3, NULL, $4, '', 0, '') RETURNING word_id;";
+        let word_id: i64 = sqlx::query(query)
+            .bind(seq)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(word)
+            .bind(i32::try_from(word_type).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+
+        self.update_log_trx(
+            UpdateType::InsertWord,
+            Some(word_id),
+            None,
+            None,
+            format!(
+                "Insert word ({}) {} of type {} before word ({}) in text ({})",
+                word_id, word, word_type, before_word_id, text_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(Some(u32::try_from(word_id).unwrap()))
+    }
+
+    async fn update_word(
+        &mut self,
+        word_id: u32,
+        word: Option<&str>,
+        word_type: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT word, COALESCE(type, 0) AS type FROM words WHERE word_id = $1;";
+        let old: Option<(String, u32)> = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    rec.get("word"),
+                    u32::try_from(rec.get::<i32, _>("type")).unwrap(),
+                )
+            })
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((old_word, old_type)) = old else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE word_id = $1 RETURNING word_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
             .fetch_one(&mut *self.tx)
             .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+        self.update_log_trx(
+            UpdateType::EditWord,
+            Some(word_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Edit word ({}) from {} of type {} to {} of type {}",
+                word_id,
+                old_word,
+                old_type,
+                word.unwrap_or(&old_word),
+                word_type.unwrap_or(old_type)
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query = "UPDATE words SET word = COALESCE($1, word), type = COALESCE($2, type) WHERE word_id = $3;";
+        let res = sqlx::query(query)
+            .bind(word)
+            .bind(word_type.map(|t| i32::try_from(t).unwrap()))
+            .bind(i32::try_from(word_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
             .map_err(map_sqlx_error)?;
-        let word_id: i32 = word_id_row.get(0);
-        //.last_insert_rowid();
 
-        Ok(word_id as i64)
+        Ok(res.rows_affected())
+    }
+
+    async fn delete_word(
+        &mut self,
+        word_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT word, seq, text_id FROM words WHERE word_id = $1;";
+        let old: Option<(String, i32, u32)> = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    rec.get("word"),
+                    rec.get("seq"),
+                    u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
+                )
+            })
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((word, seq, text_id)) = old else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE word_id = $1 RETURNING word_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+        for query in [
+            "DELETE FROM latex_page_breaks WHERE word_id = $1;",
+            "DELETE FROM appcrit WHERE word_id = $1;",
+            "DELETE FROM arrowed_words WHERE word_id = $1;",
+        ] {
+            sqlx::query(query)
+                .bind(i32::try_from(word_id).unwrap())
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        let query = "DELETE FROM words WHERE word_id = $1;";
+        let rows = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+        let query = "UPDATE words SET seq = seq - 1 WHERE text_id = $1 AND seq > $2;";
+        sqlx::query(query)
+            .bind(i32::try_from(text_id).unwrap())
+            .bind(seq)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::DeleteWord,
+            Some(word_id.into()),
+            Some(history_id),
+            None,
+            format!("Delete word ({}) {} from text ({})", word_id, word, text_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_word_flag(
+        &mut self,
+        word_id: u32,
+        flagged: bool,
+        note: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT word FROM words WHERE word_id = $1;";
+        let word: Option<String> = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .map(|rec: PgRow| rec.get("word"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some(word) = word else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE word_id = $1 RETURNING word_history_id;";
+        let history_id: i64 = sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .get::<i32, _>(0)
+            .into();
+        let (update_type, desc) = if flagged {
+            (UpdateType::FlagWord, "Flag")
+        } else {
+            (UpdateType::UnflagWord, "Unflag")
+        };
+        self.update_log_trx(
+            update_type,
+            Some(word_id.into()),
+            Some(history_id),
+            None,
+            format!("{} word ({}) {}: {}", desc, word_id, word, note).as_str(),
+            info,
+        )
+        .await?;
+
+        let query = "UPDATE words SET isFlagged = $1, note = $2 WHERE word_id = $3;";
+        let res = sqlx::query(query)
+            .bind(i32::from(flagged))
+            .bind(note)
+            .bind(i32::try_from(word_id).unwrap())
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_flagged_words(
+        &mut self,
+        course_id: u32,
+        text_id: Option<u32>,
+    ) -> Result<Vec<FlaggedWordRow>, GlosserError> {
+        let query = "SELECT A.word_id, A.text_id, T.name, A.seq, A.word, A.note, \
+        (SELECT COALESCE(STRING_AGG(W.word, ' ' ORDER BY W.seq), '') FROM words W \
+        WHERE W.text_id = A.text_id AND W.seq BETWEEN A.seq - 3 AND A.seq + 3 AND W.word <> '') AS context \
+        FROM words A \
+        INNER JOIN texts T ON A.text_id = T.text_id \
+        INNER JOIN course_x_text B ON A.text_id = B.text_id AND B.course_id = $1 \
+        WHERE A.isFlagged = 1 AND ($2::INTEGER IS NULL OR A.text_id = $2) \
+        ORDER BY B.text_order, A.seq;";
+        sqlx::query(query)
+            .bind(i32::try_from(course_id).unwrap())
+            .bind(text_id.map(|t| i32::try_from(t).unwrap()))
+            .map(|rec: PgRow| FlaggedWordRow {
+                word_id: u32::try_from(rec.get::<i32, _>("word_id")).unwrap(),
+                text_id: u32::try_from(rec.get::<i32, _>("text_id")).unwrap(),
+                text_name: rec.get("name"),
+                seq: u32::try_from(rec.get::<i32, _>("seq")).unwrap(),
+                word: rec.get("word"),
+                note: rec.get("note"),
+                context: rec.get("context"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_word_arrows(&mut self, word_id: u32) -> Result<Vec<(u32, u32)>, GlosserError> {
+        let query =
+            "SELECT course_id, gloss_id FROM arrowed_words WHERE word_id = $1 ORDER BY course_id;";
+        sqlx::query(query)
+            .bind(i32::try_from(word_id).unwrap())
+            .map(|rec: PgRow| {
+                (
+                    u32::try_from(rec.get::<i32, _>("course_id")).unwrap(),
+                    u32::try_from(rec.get::<i32, _>("gloss_id")).unwrap(),
+                )
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
     }
 
     async fn create_db(&mut self) -> Result<(), GlosserError> {
@@ -1819,17 +4098,47 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
-        let query = r#"CREATE TABLE IF NOT EXISTS course_x_text (course_id INTEGER NOT NULL REFERENCES courses (course_id), text_id INTEGER NOT NULL REFERENCES texts (text_id), text_order INTEGER NOT NULL, PRIMARY KEY (course_id, text_id));"#;
+        let query = r#"CREATE TABLE IF NOT EXISTS texts_history (text_history_id SERIAL PRIMARY KEY, text_id INTEGER NOT NULL, name TEXT NOT NULL, parent_id INTEGER, display INTEGER, title TEXT NOT NULL, updated INTEGER, user_id INTEGER);"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS course_x_text (course_id INTEGER NOT NULL REFERENCES courses (course_id), text_id INTEGER NOT NULL REFERENCES texts (text_id), text_order INTEGER NOT NULL, parent_id INTEGER, PRIMARY KEY (course_id, text_id));"#;
         let _res = sqlx::query(query)
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
-        let query = r#"CREATE TABLE IF NOT EXISTS glosses (gloss_id SERIAL PRIMARY KEY, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '');"#;
+        let query = r#"CREATE TABLE IF NOT EXISTS glosses (gloss_id SERIAL PRIMARY KEY, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '', principal_parts TEXT, genitive TEXT, gender TEXT, terminations TEXT, sortkey TEXT NOT NULL DEFAULT '');"#;
         let _res = sqlx::query(query)
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
         let query = r#"CREATE TABLE IF NOT EXISTS words (word_id SERIAL PRIMARY KEY, seq INTEGER NOT NULL, text_id INTEGER NOT NULL, word TEXT NOT NULL, gloss_id INTEGER DEFAULT NULL REFERENCES glosses (gloss_id), type INTEGER DEFAULT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, updatedUser TEXT NOT NULL DEFAULT '', isFlagged INTEGER NOT NULL DEFAULT 0, note TEXT NOT NULL DEFAULT '');"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS course_x_gloss (course_id INTEGER NOT NULL REFERENCES courses (course_id), gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), def TEXT, note TEXT, updated INTEGER, user_id INTEGER REFERENCES users (user_id), PRIMARY KEY (course_id, gloss_id));"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS tags (tag_id SERIAL PRIMARY KEY, name TEXT NOT NULL UNIQUE);"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS gloss_x_tag (gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), tag_id INTEGER NOT NULL REFERENCES tags (tag_id), PRIMARY KEY (gloss_id, tag_id));"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS course_baselines (course_id INTEGER NOT NULL PRIMARY KEY REFERENCES courses (course_id), max_unit INTEGER NOT NULL DEFAULT 0);"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS course_known_glosses (course_id INTEGER NOT NULL REFERENCES courses (course_id), gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), PRIMARY KEY (course_id, gloss_id));"#;
         let _res = sqlx::query(query)
             .execute(&mut *self.tx)
             .await
@@ -1854,7 +4163,7 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
-        let query = r#"CREATE TABLE IF NOT EXISTS glosses_history (gloss_history_id SERIAL PRIMARY KEY, gloss_id INTEGER NOT NULL, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '');"#;
+        let query = r#"CREATE TABLE IF NOT EXISTS glosses_history (gloss_history_id SERIAL PRIMARY KEY, gloss_id INTEGER NOT NULL, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '', principal_parts TEXT, genitive TEXT, gender TEXT, terminations TEXT);"#;
         let _res = sqlx::query(query)
             .execute(&mut *self.tx)
             .await
@@ -1875,6 +4184,16 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .await
             .map_err(map_sqlx_error)?;
         let query = r#"CREATE TABLE IF NOT EXISTS containers (container_id SERIAL PRIMARY KEY, name TEXT NOT NULL);"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS course_x_container (course_id INTEGER NOT NULL REFERENCES courses (course_id), container_id INTEGER NOT NULL REFERENCES containers (container_id), container_order INTEGER NOT NULL, PRIMARY KEY (course_id, container_id));"#;
+        let _res = sqlx::query(query)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query = r#"CREATE TABLE IF NOT EXISTS container_x_text (course_id INTEGER NOT NULL, text_id INTEGER NOT NULL REFERENCES texts (text_id), container_id INTEGER NOT NULL, PRIMARY KEY (course_id, text_id), FOREIGN KEY (course_id, container_id) REFERENCES course_x_container (course_id, container_id));"#;
         let _res = sqlx::query(query)
             .execute(&mut *self.tx)
             .await
@@ -1921,6 +4240,17 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             .await
             .map_err(map_sqlx_error)?;
 
+        for (table, column, column_type) in ADDED_COLUMNS {
+            let query = format!(
+                "ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {};",
+                table, column, column_type
+            );
+            sqlx::query(&query)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
         //create default course
         let query = r#"INSERT INTO courses VALUES (1, 'Greek') ON CONFLICT DO NOTHING;"#;
         sqlx::query(query)
@@ -1938,6 +4268,30 @@ impl GlosserDbTrx for GlosserDbPostgresTrx<'_> {
             (5, "Set gloss"),
             (6, "Import text"),
             (7, "Delete gloss"),
+            (8, "Edit course gloss"),
+            (9, "Edit tag"),
+            (10, "Tag gloss"),
+            (11, "Set gloss status"),
+            (12, "Import glosses"),
+            (13, "Purge gloss"),
+            (14, "Set gloss unit"),
+            (15, "Recompute sortalpha"),
+            (16, "Clone course"),
+            (17, "Set course baseline"),
+            (18, "Nest text"),
+            (19, "Edit container"),
+            (20, "Set text container"),
+            (21, "Attach text"),
+            (22, "Detach text"),
+            (23, "Edit text"),
+            (24, "Delete text"),
+            (25, "Split text"),
+            (26, "Merge texts"),
+            (27, "Edit word"),
+            (28, "Insert word"),
+            (29, "Delete word"),
+            (30, "Flag word"),
+            (31, "Unflag word"),
         ];
 
         for t in update_types {
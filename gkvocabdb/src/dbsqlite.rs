@@ -16,19 +16,40 @@ GNU General Public License for more details.
 You should have received a copy of the GNU General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
+use crate::ADDED_COLUMNS;
+use crate::ArrowHistoryEntry;
+use crate::ArrowedWordRow;
 use crate::AssignmentRow;
 use crate::AssignmentTree;
+use crate::AutoArrowRow;
 use crate::ConnectionInfo;
+use crate::CourseBaseline;
+use crate::CourseTextRow;
+use crate::DeletedGlossRow;
+use crate::FlaggedWordRow;
 use crate::GlossEntry;
 use crate::GlossOccurrence;
+use crate::GlossParts;
+use crate::GlossRegex;
+use crate::GlossReviewRow;
+use crate::GlossStatus;
+use crate::GlossaryRow;
 use crate::GlosserDb;
 use crate::GlosserDbTrx;
 use crate::GlosserError;
 use crate::LemmatizerRecord;
 use crate::SmallWord;
+use crate::Tag;
+use crate::TagFilter;
+use crate::TextArrowRow;
+use crate::TextGlossCount;
 use crate::TextWord;
 use crate::UpdateType;
 use crate::WordRow;
+use crate::WordType;
+use crate::greek::make_sortalpha;
+use crate::greek::sort_key;
+use crate::reorder_texts;
 use secrecy::ExposeSecret;
 use secrecy::Secret;
 use sqlx::Transaction;
@@ -36,7 +57,6 @@ use sqlx::sqlite::SqliteRow;
 use sqlx::{Row, SqlitePool};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use unicode_normalization::UnicodeNormalization;
 
 /*
 pub async fn get_seq_by_prefix(pool: &SqlitePool, table:&str, prefix:&str) -> Result<u32, GlosserError> {
@@ -94,6 +114,47 @@ pub fn map_sqlx_error(err: sqlx::Error) -> GlosserError {
     }
 }
 
+fn gloss_parts_from_row(rec: &SqliteRow) -> GlossParts {
+    GlossParts {
+        principal_parts: rec.get("principal_parts"),
+        genitive: rec.get("genitive"),
+        gender: rec.get("gender"),
+        terminations: rec.get("terminations"),
+    }
+}
+
+//a status we don't know about is treated as not yet approved
+fn gloss_status_from_row(rec: &SqliteRow) -> GlossStatus {
+    GlossStatus::from_i32(rec.get::<i32, _>("status")).unwrap_or(GlossStatus::Draft)
+}
+
+fn glossary_row(rec: &SqliteRow) -> GlossaryRow {
+    GlossaryRow {
+        gloss_id: Some(rec.get("gloss_id")),
+        lemma: rec.get("lemma"),
+        sortalpha: rec.get("sortalpha"),
+        pos: rec.get("pos"),
+        def: rec.get("def"),
+        note: rec.get("note"),
+        unit: Some(rec.get("unit")),
+        status: Some(gloss_status_from_row(rec)),
+        principal_parts: rec.get("principal_parts"),
+        genitive: rec.get("genitive"),
+        gender: rec.get("gender"),
+        terminations: rec.get("terminations"),
+    }
+}
+
+fn gloss_review_row(rec: &SqliteRow) -> GlossReviewRow {
+    GlossReviewRow {
+        gloss_id: rec.get("gloss_id"),
+        lemma: rec.get("lemma"),
+        pos: rec.get("pos"),
+        def: rec.get("def"),
+        status: gloss_status_from_row(rec),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GlosserDbSqlite {
     pub db: SqlitePool,
@@ -230,15 +291,16 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
 
     async fn get_hqvocab_column(
         &mut self,
+        course_id: u32,
         pos: &str,
         lower_unit: u32,
         unit: u32,
         sort: &str,
+        tags: &TagFilter,
     ) -> Result<Vec<(String, u32, String)>, GlosserError> {
-        let course_id = 1;
         let s = match sort {
-            "alpha" => "sortalpha COLLATE PolytonicGreek ASC",
-            _ => "unit, sortalpha COLLATE PolytonicGreek ASC",
+            "alpha" => "sortalpha ASC, sortkey ASC",
+            _ => "unit, sortalpha ASC, sortkey ASC",
         };
         let p = match pos {
             "noun" => "pos = 'noun'",
@@ -247,14 +309,24 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             _ => "pos != 'noun' AND pos != 'verb' AND pos != 'adjective'",
         };
         let query = format!(
-            "SELECT lemma, unit, def FROM glosses a \
+            "SELECT lemma, unit, COALESCE(cg.def, a.def) AS def, principal_parts, genitive, gender, terminations FROM glosses a \
             LEFT JOIN arrowed_words d ON (a.gloss_id = d.gloss_id AND d.course_id = {course_id}) \
-            WHERE {} AND unit >= $1 AND unit <= $2 AND status = 1 ORDER BY {};",
-            p, s
+            LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {course_id}) \
+            WHERE {} AND unit >= $1 AND unit <= $2 AND status > 0{} ORDER BY {};",
+            p,
+            tags.sql("a.gloss_id"),
+            s
         );
-        let words: Vec<(String, u32, String)> = sqlx::query_as(&query)
+        let words: Vec<(String, u32, String)> = sqlx::query(&query)
             .bind(lower_unit)
             .bind(unit)
+            .map(|rec: SqliteRow| {
+                (
+                    gloss_parts_from_row(&rec).display_lemma(rec.get("lemma")),
+                    rec.get("unit"),
+                    rec.get("def"),
+                )
+            })
             .fetch_all(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
@@ -392,6 +464,379 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         Ok(())
     }
 
+    async fn move_arrow(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+        word_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<(), GlosserError> {
+        let query = "INSERT INTO arrowed_words_history (history_id, course_id, gloss_id, word_id, updated, user_id, comment) \
+        SELECT NULL, course_id, gloss_id, word_id, updated, user_id, comment \
+        FROM arrowed_words \
+        WHERE course_id = $1 AND gloss_id = $2;";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM arrowed_words WHERE course_id = $1 AND gloss_id = $2;";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        if let Some(word_id) = word_id {
+            let query = "INSERT INTO arrowed_words (course_id, gloss_id, word_id, updated, user_id, comment) VALUES ($1, $2, $3, $4, $5, NULL);";
+            sqlx::query(query)
+                .bind(course_id)
+                .bind(gloss_id)
+                .bind(word_id)
+                .bind(info.timestamp)
+                .bind(info.user_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+        Ok(())
+    }
+
+    async fn get_course_name(&mut self, course_id: u32) -> Result<Option<String>, GlosserError> {
+        let query = "SELECT name FROM courses WHERE course_id = $1;";
+        sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| rec.get("name"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_course_text_ids(&mut self, course_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT text_id FROM course_x_text WHERE course_id = $1 ORDER BY text_order;";
+        sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| rec.get("text_id"))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn clone_course(
+        &mut self,
+        source_course_id: u32,
+        name: &str,
+        text_ids: &[u32],
+        info: &ConnectionInfo,
+    ) -> Result<(u32, u64, u64, u64), GlosserError> {
+        let query = "INSERT INTO courses (course_id, name) VALUES (NULL, $1);";
+        let course_id = sqlx::query(query)
+            .bind(name)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        //text_ids are u32s so they are safe to interpolate
+        let text_list = text_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let query = format!(
+            "INSERT INTO course_x_text (course_id, text_id, text_order, parent_id) \
+            SELECT $1, text_id, text_order, parent_id FROM course_x_text WHERE course_id = $2 AND text_id IN ({});",
+            if text_list.is_empty() {
+                "NULL"
+            } else {
+                &text_list
+            }
+        );
+        let texts = sqlx::query(&query)
+            .bind(course_id)
+            .bind(source_course_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "INSERT INTO arrowed_words (course_id, gloss_id, word_id, updated, user_id, comment) \
+        SELECT $1, d.gloss_id, d.word_id, d.updated, d.user_id, d.comment FROM arrowed_words d \
+        LEFT JOIN words w ON d.word_id = w.word_id \
+        WHERE d.course_id = $2 \
+        AND (d.word_id = 1 OR w.text_id IN (SELECT text_id FROM course_x_text WHERE course_id = $1));";
+        let arrows = sqlx::query(query)
+            .bind(course_id)
+            .bind(source_course_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "INSERT INTO course_x_gloss (course_id, gloss_id, def, note, updated, user_id) \
+        SELECT $1, gloss_id, def, note, updated, user_id FROM course_x_gloss WHERE course_id = $2;";
+        let glosses = sqlx::query(query)
+            .bind(course_id)
+            .bind(source_course_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        for query in [
+            "INSERT INTO course_baselines (course_id, max_unit) \
+            SELECT $1, max_unit FROM course_baselines WHERE course_id = $2;",
+            "INSERT INTO course_known_glosses (course_id, gloss_id) \
+            SELECT $1, gloss_id FROM course_known_glosses WHERE course_id = $2;",
+            //containers are shared with the source course, as are their cloned texts' places in them
+            "INSERT INTO course_x_container (course_id, container_id, container_order) \
+            SELECT $1, container_id, container_order FROM course_x_container WHERE course_id = $2;",
+            "INSERT INTO container_x_text (course_id, text_id, container_id) \
+            SELECT $1, text_id, container_id FROM container_x_text WHERE course_id = $2 \
+            AND text_id IN (SELECT text_id FROM course_x_text WHERE course_id = $1);",
+        ] {
+            sqlx::query(query)
+                .bind(course_id)
+                .bind(source_course_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.update_log_trx(
+            UpdateType::CloneCourse,
+            Some(course_id),
+            None,
+            Some(course_id),
+            format!(
+                "Cloned course ({}) from course ({}): {} texts, {} arrows, {} course glosses",
+                course_id, source_course_id, texts, arrows, glosses
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok((u32::try_from(course_id).unwrap(), texts, arrows, glosses))
+    }
+
+    async fn get_course_baseline(
+        &mut self,
+        course_id: u32,
+    ) -> Result<CourseBaseline, GlosserError> {
+        let query = "SELECT max_unit FROM course_baselines WHERE course_id = $1;";
+        let max_unit = sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| rec.get("max_unit"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .unwrap_or(0);
+
+        let query =
+            "SELECT gloss_id FROM course_known_glosses WHERE course_id = $1 ORDER BY gloss_id;";
+        let gloss_ids = sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| rec.get("gloss_id"))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(CourseBaseline {
+            max_unit,
+            gloss_ids,
+        })
+    }
+
+    async fn set_course_baseline(
+        &mut self,
+        course_id: u32,
+        baseline: &CourseBaseline,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "REPLACE INTO course_baselines (course_id, max_unit) VALUES ($1, $2);";
+        let mut rows = sqlx::query(query)
+            .bind(course_id)
+            .bind(baseline.max_unit)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "DELETE FROM course_known_glosses WHERE course_id = $1;";
+        sqlx::query(query)
+            .bind(course_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query =
+            "INSERT OR IGNORE INTO course_known_glosses (course_id, gloss_id) VALUES ($1, $2);";
+        for gloss_id in &baseline.gloss_ids {
+            rows += sqlx::query(query)
+                .bind(course_id)
+                .bind(*gloss_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected();
+        }
+
+        self.update_log_trx(
+            UpdateType::SetCourseBaseline,
+            None,
+            None,
+            Some(course_id.into()),
+            format!(
+                "Set baseline of course ({}) to units 1-{} and {} known glosses",
+                course_id,
+                baseline.max_unit,
+                baseline.gloss_ids.len()
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_known_gloss_ids(&mut self, course_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT gloss_id FROM glosses \
+        WHERE unit BETWEEN 1 AND (SELECT max_unit FROM course_baselines WHERE course_id = $1) \
+        UNION SELECT gloss_id FROM course_known_glosses WHERE course_id = $1;";
+        sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| rec.get("gloss_id"))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_arrowed_words(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<ArrowedWordRow>, GlosserError> {
+        let query = "SELECT d.gloss_id, d.word_id, w.gloss_id AS word_gloss_id, c.text_id IS NOT NULL AS in_course \
+        FROM arrowed_words d \
+        LEFT JOIN words w ON d.word_id = w.word_id \
+        LEFT JOIN course_x_text c ON (w.text_id = c.text_id AND c.course_id = d.course_id) \
+        WHERE d.course_id = $1 AND d.word_id != 1 \
+        ORDER BY d.gloss_id, d.word_id;";
+        sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| ArrowedWordRow {
+                gloss_id: rec.get("gloss_id"),
+                word_id: rec.get("word_id"),
+                word_gloss_id: rec.get("word_gloss_id"),
+                in_course: rec.get("in_course"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_arrow_history(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+    ) -> Result<Vec<ArrowHistoryEntry>, GlosserError> {
+        let query = "SELECT h.history_id, h.word_id, w.word, h.updated, u.initials \
+        FROM arrowed_words_history h \
+        LEFT JOIN words w ON h.word_id = w.word_id \
+        LEFT JOIN users u ON h.user_id = u.user_id \
+        WHERE h.course_id = $1 AND h.gloss_id = $2 \
+        ORDER BY h.history_id;";
+        let mut history = sqlx::query(query)
+            .bind(course_id)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| ArrowHistoryEntry {
+                history_id: rec.get("history_id"),
+                from_word_id: None,
+                word_id: rec.get("word_id"),
+                word: rec.get("word"),
+                updated: rec.get("updated"),
+                user: rec.get("initials"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "SELECT d.word_id, w.word, d.updated, u.initials \
+        FROM arrowed_words d \
+        LEFT JOIN words w ON d.word_id = w.word_id \
+        LEFT JOIN users u ON d.user_id = u.user_id \
+        WHERE d.course_id = $1 AND d.gloss_id = $2;";
+        let current = sqlx::query(query)
+            .bind(course_id)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| ArrowHistoryEntry {
+                history_id: None,
+                from_word_id: None,
+                word_id: rec.get("word_id"),
+                word: rec.get("word"),
+                updated: rec.get("updated"),
+                user: rec.get("initials"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        history.extend(current);
+
+        Ok(history)
+    }
+
+    async fn get_gloss_occurrence_order(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<(u32, u32)>, GlosserError> {
+        let query = "SELECT a.gloss_id, a.word_id FROM words a \
+        INNER JOIN course_x_text c ON (a.text_id = c.text_id AND c.course_id = $1) \
+        WHERE a.gloss_id IS NOT NULL AND a.type = $2 \
+        ORDER BY c.text_order, a.seq;";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(WordType::Word as i32)
+            .map(|rec: SqliteRow| (rec.get("gloss_id"), rec.get("word_id")))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_unarrowed_occurrences(
+        &mut self,
+        course_id: u32,
+        skip_units_through: u32,
+        tags: &TagFilter,
+    ) -> Result<Vec<AutoArrowRow>, GlosserError> {
+        let query = format!(
+            "SELECT a.word_id, a.word, a.text_id, a.gloss_id, g.lemma FROM words a \
+            INNER JOIN course_x_text c ON (a.text_id = c.text_id AND c.course_id = $1) \
+            INNER JOIN glosses g ON a.gloss_id = g.gloss_id \
+            LEFT JOIN arrowed_words d ON (d.course_id = $1 AND d.gloss_id = a.gloss_id) \
+            WHERE d.gloss_id IS NULL AND g.status > 0 AND a.type = $2 \
+            AND NOT (g.unit BETWEEN 1 AND $3){} \
+            ORDER BY c.text_order, a.seq;",
+            tags.sql("a.gloss_id")
+        );
+        sqlx::query(&query)
+            .bind(course_id)
+            .bind(WordType::Word as i32)
+            .bind(skip_units_through)
+            .map(|rec: SqliteRow| AutoArrowRow {
+                gloss_id: rec.get("gloss_id"),
+                lemma: rec.get("lemma"),
+                word_id: rec.get("word_id"),
+                word: rec.get("word"),
+                text_id: rec.get("text_id"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
     //word_id is unique across courses, so we do not need to use course_id except for where the word is arrowed
     //to do: we need to send back updated counts both for the new gloss_id and for the old gloss_id, if one was set
     //to do: can we limit what is sent back by the text being viewed?
@@ -467,12 +912,13 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {course_id}
             GROUP BY gloss_id
         )
-        SELECT B.gloss_id, B.lemma, B.pos, B.def, total_count, A.seq, A.word_id, \
+        SELECT B.gloss_id, B.lemma, B.pos, COALESCE(CG.def, B.def) AS def, total_count, A.seq, A.word_id, \
     D.word_id as arrowedID, E.seq AS arrowedSeq, A.isFlagged, G.text_order,F.text_order AS arrowed_text_order, \
     COUNT(A.gloss_id) OVER (PARTITION BY A.gloss_id ORDER BY G.text_order,A.seq ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)
         AS running_count
     FROM words A \
     LEFT JOIN glosses B ON A.gloss_id = B.gloss_id \
+    LEFT JOIN course_x_gloss CG ON (A.gloss_id = CG.gloss_id AND CG.course_id = {course_id}) \
     LEFT JOIN arrowed_words D ON (A.gloss_id = D.gloss_id AND D.course_id = {course_id}) \
     LEFT JOIN words E ON E.word_id = D.word_id \
     LEFT JOIN course_x_text F ON (E.text_id = F.text_id AND F.course_id = {course_id}) \
@@ -541,14 +987,12 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         //arrow integer NOT NULL DEFAULT 0, flagged integer NOT NULL DEFAULT 0, updated timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
         //updatedUserAgent varchar (255) NOT NULL DEFAULT '', updatedIP varchar (255) NOT NULL DEFAULT '', updatedUser varchar (255) NOT NULL DEFAULT '', isFlagged integer NOT NULL DEFAULT 0, note varchar (1024) NOT NULL DEFAULT '')
 
-        let mut seq: u32 = 1;
-
         let query = "INSERT INTO words (word_id, seq, text_id, word, gloss_id, \
         type, updated, updatedUser, isFlagged, note) \
         VALUES (NULL, $1, $2, $3, $4, $5, $6, $7, 0, '');";
         let mut count = 0;
         let mut gloss_ids: HashSet<u32> = HashSet::new();
-        for w in words {
+        for (seq, w) in (1_u32..).zip(words.into_iter()) {
             let res = sqlx::query(query)
                 .bind(seq)
                 .bind(text_id)
@@ -565,8 +1009,6 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
                 gloss_ids.insert(g_id);
             }
 
-            seq += 1;
-
             let affected_rows = res.rows_affected();
             if affected_rows != 1 {
                 return Err(GlosserError::UnknownError);
@@ -607,25 +1049,22 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         Ok((count, i32::try_from(text_id).unwrap()))
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn insert_gloss(
         &mut self,
         gloss: &str,
         pos: &str,
         def: &str,
-        stripped_lemma: &str,
         note: &str,
+        unit: u32,
+        parts: &GlossParts,
         info: &ConnectionInfo,
     ) -> Result<(i64, u64), GlosserError> {
         let query = "INSERT INTO glosses (gloss_id, unit, lemma, sortalpha, \
-        def, pos, note, updated, status, updatedUser) \
-        VALUES (NULL, 0, $1, $2, $3, $4, $5, $6, 1, $7);";
+        def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations, sortkey) \
+        VALUES (NULL, $12, $1, $2, $3, $4, $5, $6, $14, $7, $8, $9, $10, $11, $13);";
 
-        //double check that diacritics are stripped and word is lowercased; doesn't handle pua here yet
-        let sl = stripped_lemma
-            .nfd()
-            .filter(|x| !unicode_normalization::char::is_combining_mark(*x))
-            .collect::<String>()
-            .to_lowercase();
+        let sl = make_sortalpha(gloss);
 
         let res = sqlx::query(query)
             .bind(gloss)
@@ -635,6 +1074,13 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             .bind(note)
             .bind(info.timestamp)
             .bind(info.user_id)
+            .bind(&parts.principal_parts)
+            .bind(&parts.genitive)
+            .bind(&parts.gender)
+            .bind(&parts.terminations)
+            .bind(unit)
+            .bind(sort_key(gloss))
+            .bind(GlossStatus::Draft.value())
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
@@ -654,31 +1100,86 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         Ok((new_gloss_id, res.rows_affected()))
     }
 
-    async fn update_log_trx(
+    async fn set_gloss_unit(
         &mut self,
-        update_type: UpdateType,
-        object_id: Option<i64>,
-        history_id: Option<i64>,
-        course_id: Option<i64>,
-        update_desc: &str,
+        gloss_id: u32,
+        unit: u32,
         info: &ConnectionInfo,
-    ) -> Result<(), GlosserError> {
-        let query = "INSERT INTO update_log \
-        (update_id, update_type, object_id, history_id, course_id, update_desc, updated, user_id, ip, user_agent) \
-        VALUES (NULL, $1, $2, $3, $4, $5, $6, $7, $8, $9);";
-        sqlx::query(query)
-            .bind(update_type.value())
-            .bind(object_id)
-            .bind(history_id)
-            .bind(course_id)
-            .bind(update_desc)
-            .bind(info.timestamp)
-            .bind(info.user_id)
-            .bind(&info.ip_address)
-            .bind(&info.user_agent)
-            .execute(&mut *self.tx)
-            .await
-            .map_err(map_sqlx_error)?;
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT unit FROM glosses WHERE gloss_id = $1;";
+        let old_unit: u32 = sqlx::query(query)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| rec.get("unit"))
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        if old_unit == unit {
+            return Ok(0);
+        }
+
+        let query = "INSERT INTO glosses_history \
+        (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT NULL, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations FROM glosses WHERE gloss_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+
+        self.update_log_trx(
+            UpdateType::SetGlossUnit,
+            Some(gloss_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Changed unit of gloss ({}) from {} to {}",
+                gloss_id, old_unit, unit
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query =
+            "UPDATE glosses SET unit = $1, updated = $2, updatedUser = $3 WHERE gloss_id = $4;";
+        let res = sqlx::query(query)
+            .bind(unit)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn update_log_trx(
+        &mut self,
+        update_type: UpdateType,
+        object_id: Option<i64>,
+        history_id: Option<i64>,
+        course_id: Option<i64>,
+        update_desc: &str,
+        info: &ConnectionInfo,
+    ) -> Result<(), GlosserError> {
+        let query = "INSERT INTO update_log \
+        (update_id, update_type, object_id, history_id, course_id, update_desc, updated, user_id, ip, user_agent) \
+        VALUES (NULL, $1, $2, $3, $4, $5, $6, $7, $8, $9);";
+        sqlx::query(query)
+            .bind(update_type.value())
+            .bind(object_id)
+            .bind(history_id)
+            .bind(course_id)
+            .bind(update_desc)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .bind(&info.ip_address)
+            .bind(&info.user_agent)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
 
         Ok(())
     }
@@ -696,20 +1197,32 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             .map_err(map_sqlx_error)?;
 
         if count.0 == 0 {
-            //jwm2
+            //saved so a restore can put back the status it had
+            let query = "INSERT INTO glosses_history \
+            (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+            SELECT NULL, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations FROM glosses WHERE gloss_id = $1;";
+            let history_id = sqlx::query(query)
+                .bind(gloss_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .last_insert_rowid();
+
             self.update_log_trx(
                 UpdateType::DeleteGloss,
                 Some(gloss_id.into()),
-                Some(gloss_id.into()),
+                Some(history_id),
                 None,
                 format!("Deleted gloss ({})", gloss_id).as_str(),
                 info,
             )
             .await?;
 
-            let query = "UPDATE glosses SET status = 0 WHERE gloss_id = $1;";
+            let query = "UPDATE glosses SET status = 0, updated = $2, updatedUser = $3 WHERE gloss_id = $1;";
             let res = sqlx::query(query)
                 .bind(gloss_id)
+                .bind(info.timestamp)
+                .bind(info.user_id)
                 .execute(&mut *self.tx)
                 .await
                 .map_err(map_sqlx_error)?;
@@ -727,13 +1240,13 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         gloss: &str,
         pos: &str,
         def: &str,
-        stripped_gloss: &str,
         note: &str,
+        parts: &GlossParts,
         info: &ConnectionInfo,
     ) -> Result<u64, GlosserError> {
         let query = "INSERT INTO glosses_history \
-        (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser) \
-        SELECT NULL, * FROM glosses WHERE gloss_id = $1;";
+        (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT NULL, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations FROM glosses WHERE gloss_id = $1;";
         let history_id = sqlx::query(query)
             .bind(gloss_id)
             .execute(&mut *self.tx)
@@ -757,22 +1270,22 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
 
         //CREATE TABLE IF NOT EXISTS update_log (update_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, update_type INTEGER REFERENCES update_types(update_type_id), object_id INTEGER, history_id INTEGER, course_id INTEGER, update_desc TEXT, comment TEXT, updated INTEGER NOT NULL, user_id INTEGER REFERENCES users(user_id), ip TEXT, user_agent TEXT );
 
-        //double check that diacritics are stripped and word is lowercased; doesn't handle pua here yet
-        let sl = stripped_gloss
-            .nfd()
-            .filter(|x| !unicode_normalization::char::is_combining_mark(*x))
-            .collect::<String>()
-            .to_lowercase();
+        let sl = make_sortalpha(gloss);
 
         let query = "UPDATE glosses SET \
         lemma = $1, \
         sortalpha = $2, \
+        sortkey = $13, \
         def = $3, \
         pos = $4, \
         note = $5, \
         updated = $6, \
-        updatedUser = $7 \
-        WHERE gloss_id = $8;";
+        updatedUser = $7, \
+        principal_parts = NULLIF(COALESCE($8, principal_parts), ''), \
+        genitive = NULLIF(COALESCE($9, genitive), ''), \
+        gender = NULLIF(COALESCE($10, gender), ''), \
+        terminations = NULLIF(COALESCE($11, terminations), '') \
+        WHERE gloss_id = $12;";
 
         let res = sqlx::query(query)
             .bind(gloss)
@@ -782,7 +1295,12 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             .bind(note)
             .bind(info.timestamp)
             .bind(info.user_id)
+            .bind(&parts.principal_parts)
+            .bind(&parts.genitive)
+            .bind(&parts.gender)
+            .bind(&parts.terminations)
             .bind(gloss_id)
+            .bind(sort_key(gloss))
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
@@ -867,11 +1385,12 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         course_id: u32,
     ) -> Result<Vec<WordRow>, GlosserError> {
         let query = format!(
-            "SELECT a.word_id, a.word, a.type, b.lemma, b.def, b.sortalpha, b.unit, b.pos, d.word_id as arrowedID, \
-        b.gloss_id, a.seq, e.seq AS arrowedSeq, \
+            "SELECT a.word_id, a.word, a.type, b.lemma, COALESCE(cg.def, b.def) AS def, NULLIF(COALESCE(cg.note, b.note), '') AS note, b.sortalpha, b.unit, b.pos, d.word_id as arrowedID, \
+        b.gloss_id, a.seq, e.seq AS arrowedSeq, b.principal_parts, b.genitive, b.gender, b.terminations, \
         a.isFlagged, g.text_order, f.text_order AS arrowed_text_order, c.word_id as page_break, h.entry AS appcrit_entry \
         FROM words a \
         LEFT JOIN glosses b ON a.gloss_id = b.gloss_id \
+        LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {course_id}) \
         LEFT JOIN latex_page_breaks c ON a.word_id = c.word_id \
         LEFT JOIN arrowed_words d ON (a.gloss_id = d.gloss_id AND d.course_id = {course_id}) \
         LEFT JOIN words e ON e.word_id = d.word_id \
@@ -890,8 +1409,11 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
                 wordid: rec.get("word_id"),
                 word: rec.get("word"),
                 word_type: rec.get("type"),
-                lemma: rec.get("lemma"),
+                lemma: rec
+                    .get::<Option<String>, _>("lemma")
+                    .map(|l| gloss_parts_from_row(&rec).display_lemma(&l)),
                 def: rec.get("def"),
+                note: rec.get("note"),
                 unit: rec.get("unit"),
                 pos: rec.get("pos"),
                 arrowed_id: rec.get("arrowedID"),
@@ -945,12 +1467,13 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {course_id}
             GROUP BY gloss_id
         )
-        SELECT a.word_id, a.word, a.type, b.lemma, b.def, b.unit, b.pos, b.sortalpha, d.word_id as arrowedID, b.gloss_id, a.seq, e.seq AS arrowedSeq,
+        SELECT a.word_id, a.word, a.type, b.lemma, COALESCE(cg.def, b.def) AS def, NULLIF(COALESCE(cg.note, b.note), '') AS note, b.unit, b.pos, b.sortalpha, d.word_id as arrowedID, b.gloss_id, a.seq, e.seq AS arrowedSeq,
         a.isFlagged, g.text_order, f.text_order AS arrowed_text_order, total_count, c.word_id as page_break,
         COUNT(a.gloss_id) OVER (PARTITION BY a.gloss_id ORDER BY a.seq ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW)
         + IFNULL(running_basis, 0) AS running_count
         FROM words a
         LEFT JOIN glosses b ON a.gloss_id = b.gloss_id
+        LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {course_id})
         LEFT JOIN latex_page_breaks c ON a.word_id = c.word_id
         LEFT JOIN arrowed_words d ON (a.gloss_id = d.gloss_id AND d.course_id = {course_id})
         LEFT JOIN words e ON e.word_id = d.word_id
@@ -969,6 +1492,7 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
                 word_type: rec.get("type"),
                 lemma: rec.get("lemma"),
                 def: rec.get("def"),
+                note: rec.get("note"),
                 unit: rec.get("unit"),
                 pos: rec.get("pos"),
                 arrowed_id: rec.get("arrowedID"),
@@ -1049,6 +1573,66 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         Ok(res.0)
     }
 
+    async fn update_text(
+        &mut self,
+        text_id: u32,
+        name: &str,
+        title: Option<&str>,
+        display: Option<bool>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT name, title FROM texts WHERE text_id = $1;";
+        let old: Option<(String, String)> = sqlx::query(query)
+            .bind(text_id)
+            .map(|rec: SqliteRow| (rec.get("name"), rec.get("title")))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((old_name, old_title)) = old else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO texts_history (text_history_id, text_id, name, parent_id, display, title, updated, user_id) \
+        SELECT NULL, text_id, name, parent_id, display, title, $2, $3 FROM texts WHERE text_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(text_id)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        self.update_log_trx(
+            UpdateType::EditText,
+            Some(text_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Edit text ({}) name from {} to {}, title from {} to {}",
+                text_id,
+                old_name,
+                name,
+                old_title,
+                title.unwrap_or(&old_title)
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query = "UPDATE texts SET name = $1, title = COALESCE($2, title), display = COALESCE($3, display) WHERE text_id = $4;";
+        let res = sqlx::query(query)
+            .bind(name)
+            .bind(title)
+            .bind(display.map(i32::from))
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
     // async fn update_counts_for_text_trx<'a, 'b>(
     //     tx: &'a mut sqlx::Transaction<'b, sqlx::Sqlite>,
     //     course_id: u32,
@@ -1230,101 +1814,104 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         text_id: u32,
         step: i32,
     ) -> Result<(), GlosserError> {
-        /*
-        //has children? move children with parent
-        let query = "SELECT a.text_id,b.text_order FROM texts a \
-                    INNER JOIN course_x_text b ON a.text_id=b.text_id \
-                    WHERE parent_id = ? ORDER BY b.text_order;";
-        let children: Vec<(i32,i32,)> = sqlx::query_as(query)
-            .bind(text_id)
-            .fetch_all(pool).await?;
-        println!("children: {:?}", children);
-        let num_to_move = 1 + children.len();
-        if !children.empty() {
-        }
-        //has parent? only move among siblings
-        let query = "SELECT parent_id FROM texts WHERE text_id = ?;";
-        let has_parent: (Option<i32>,) = sqlx::query_as(query)
-            .bind(text_id)
-            .fetch_one(pool).await?;
-        println!("parent: {:?}", has_parent);
-        containers
-            container_id
-            name
-        containers_x_text
-            container_id
-            text_id
-        container moving down: container and its children get + 1, following text gets - 1 or following container + children get -1
-        container moving up: container and its children get - 1, text above gets + num_children + 1
-        text where moving is child and moving up?
-        */
-
-        // get text order int
-        let query = "SELECT text_order FROM course_x_text WHERE course_id = $1 AND text_id = $2;";
-        let text_order: (i32,) = sqlx::query_as(query)
-            .bind(course_id)
-            .bind(text_id)
-            .fetch_one(&mut *self.tx)
-            .await
-            .map_err(map_sqlx_error)?;
+        let containers: Vec<u32> = self
+            .get_course_containers(course_id)
+            .await?
+            .into_iter()
+            .map(|c| c.0)
+            .collect();
+        let rows = self.get_course_text_tree(course_id).await?;
+        // no where to move: abort
+        let order =
+            reorder_texts(&rows, &containers, text_id, step).ok_or(GlosserError::UnknownError)?;
+        self.set_course_text_order(course_id, &order).await
+    }
 
-        // get number of texts
-        let query = "SELECT COUNT(*) FROM course_x_text WHERE course_id = $1;";
-        let text_count: (i32,) = sqlx::query_as(query)
+    async fn get_course_text_tree(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<CourseTextRow>, GlosserError> {
+        let query = "SELECT B.text_id, P.text_id AS parent_id, X.container_id \
+        FROM course_x_text B \
+        INNER JOIN texts A ON A.text_id = B.text_id \
+        LEFT JOIN course_x_text P ON (P.course_id = B.course_id AND P.text_id = B.parent_id) \
+        LEFT JOIN container_x_text X ON (X.course_id = B.course_id AND X.text_id = B.text_id) \
+        WHERE B.course_id = $1 \
+        ORDER BY B.text_order, B.text_id;";
+        sqlx::query(query)
             .bind(course_id)
-            .bind(text_id)
-            .fetch_one(&mut *self.tx)
+            .map(|rec: SqliteRow| CourseTextRow {
+                text_id: rec.get("text_id"),
+                parent_id: rec.get("parent_id"),
+                container_id: rec.get("container_id"),
+            })
+            .fetch_all(&mut *self.tx)
             .await
-            .map_err(map_sqlx_error)?;
+            .map_err(map_sqlx_error)
+    }
 
-        if step == 0
-            || (text_order.0 + step < 1 && step < 0)
-            || (text_order.0 + step > text_count.0 && step > 0)
-        {
-            return Err(GlosserError::UnknownError); // no where to move: abort
-        } else if step > 0 {
-            //make room by moving other texts up/earlier in sequence
-            let query = "UPDATE course_x_text SET text_order = text_order - 1 \
-                WHERE text_order > $1 AND text_order < $2 + $3 + 1 AND course_id = $4;";
-            sqlx::query(query)
-                .bind(text_order.0)
-                .bind(text_order.0)
-                .bind(step)
-                .bind(course_id)
-                .execute(&mut *self.tx)
-                .await
-                .map_err(map_sqlx_error)?;
-        } else {
-            //make room by moving other texts down/later in sequence
-            let query = "UPDATE course_x_text SET text_order = text_order + 1 \
-                WHERE text_order < $1 AND text_order > $2 + $3 - 1 AND course_id = $4;";
+    async fn set_course_text_order(
+        &mut self,
+        course_id: u32,
+        text_ids: &[u32],
+    ) -> Result<(), GlosserError> {
+        let query =
+            "UPDATE course_x_text SET text_order = $1 WHERE course_id = $2 AND text_id = $3;";
+        for (i, text_id) in text_ids.iter().enumerate() {
             sqlx::query(query)
-                .bind(text_order.0)
-                .bind(text_order.0)
-                .bind(step) //step will be negative here
+                .bind(i32::try_from(i + 1).unwrap())
                 .bind(course_id)
+                .bind(*text_id)
                 .execute(&mut *self.tx)
                 .await
                 .map_err(map_sqlx_error)?;
         }
-        //set new text order
-        let query = "UPDATE course_x_text SET text_order = text_order + $1 WHERE course_id = $2 AND text_id = $3;";
-        sqlx::query(query)
-            .bind(step)
+        Ok(())
+    }
+
+    async fn set_text_parent(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+        parent_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query =
+            "UPDATE course_x_text SET parent_id = $1 WHERE course_id = $2 AND text_id = $3;";
+        let rows = sqlx::query(query)
+            .bind(parent_id)
             .bind(course_id)
             .bind(text_id)
             .execute(&mut *self.tx)
             .await
-            .map_err(map_sqlx_error)?;
+            .map_err(map_sqlx_error)?
+            .rows_affected();
 
-        Ok(())
+        let desc = match parent_id {
+            Some(p) => format!("Nest text ({}) under text ({})", text_id, p),
+            None => format!("Unnest text ({})", text_id),
+        };
+        self.update_log_trx(
+            UpdateType::NestText,
+            Some(text_id.into()),
+            None,
+            Some(course_id.into()),
+            desc.as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
     }
 
     async fn get_texts_db(&mut self, course_id: u32) -> Result<Vec<AssignmentRow>, GlosserError> {
-        let query = "SELECT A.text_id, A.name, A.parent_id, B.course_id, C.name AS container \
+        let query = "SELECT A.text_id, A.name, X.container_id, B.course_id, C.name AS container, \
+        P.text_id AS course_parent_id \
         FROM texts A \
         INNER JOIN course_x_text B ON (A.text_id = B.text_id AND B.course_id = $1) \
-        LEFT JOIN containers C ON A.parent_id = C.container_id \
+        LEFT JOIN container_x_text X ON (X.course_id = B.course_id AND X.text_id = A.text_id) \
+        LEFT JOIN containers C ON X.container_id = C.container_id \
+        LEFT JOIN course_x_text P ON (P.course_id = B.course_id AND P.text_id = B.parent_id) \
         WHERE display != 0 \
         ORDER BY B.text_order, A.text_id;";
         let res: Result<Vec<AssignmentRow>, GlosserError> = sqlx::query(query)
@@ -1332,9 +1919,10 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             .map(|rec: SqliteRow| AssignmentRow {
                 text_id: rec.get("text_id"),
                 text: rec.get("name"),
-                container_id: rec.get("parent_id"),
+                container_id: rec.get("container_id"),
                 course_id: rec.get("course_id"),
                 container: rec.get("container"),
+                parent_id: rec.get("course_parent_id"),
             })
             .fetch_all(&mut *self.tx)
             .await
@@ -1342,72 +1930,1427 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
 
         res
     }
-    /*
-    async fn _get_titles(pool: &SqlitePool) -> Result<Vec<(String,u32)>, GlosserError> {
-        let query = "SELECT id,title FROM titles ORDER BY title;";
-        let res: Result<Vec<(String,u32)>, GlosserError> = sqlx::query(query)
-        .map(|rec: SqliteRow| (rec.get("id"),rec.get("title")) )
-        .fetch_all(pool)
-        .await;
 
-        res
+    async fn get_course_containers(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<(u32, String)>, GlosserError> {
+        let query = "SELECT C.container_id, C.name \
+        FROM course_x_container X \
+        INNER JOIN containers C ON C.container_id = X.container_id \
+        WHERE X.course_id = $1 \
+        ORDER BY X.container_order, C.container_id;";
+        sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| (rec.get("container_id"), rec.get("name")))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
     }
-    */
-    async fn get_text_id_for_word_id(&mut self, word_id: u32) -> Result<u32, GlosserError> {
-        let query = "SELECT text_id FROM words WHERE word_id = $1;";
 
-        let rec: (u32,) = sqlx::query_as(query)
-            .bind(word_id)
-            .fetch_one(&mut *self.tx)
+    async fn insert_container(
+        &mut self,
+        course_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u32, GlosserError> {
+        let query = "INSERT INTO containers (container_id, name) VALUES (NULL, $1);";
+        let container_id = sqlx::query(query)
+            .bind(name)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        let query = "INSERT INTO course_x_container (course_id, container_id, container_order) \
+        VALUES ($1, $2, (SELECT COALESCE(MAX(container_order), 0) + 1 FROM course_x_container WHERE course_id = $1));";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(container_id)
+            .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
 
-        Ok(rec.0)
-    }
-    /*
-    async fn old_get_text_id_for_word_id(pool: &SqlitePool, word_id:u32) -> Result<u32, GlosserError> {
-    let query = "SELECT A.id FROM assignments A INNER JOIN words B ON A.start = B.word_id INNER JOIN words C ON A.end = C.word_id WHERE B.seq <= (SELECT seq FROM words WHERE word_id = ?) AND C.seq >= (SELECT seq FROM words WHERE word_id = ?) LIMIT 1;";
-
-    let rec: (u32,) = sqlx::query_as(query)
-    .bind(word_id)
-    .bind(word_id)
-    .fetch_one(pool)
-    .await?;
+        self.update_log_trx(
+            UpdateType::EditContainer,
+            Some(container_id),
+            None,
+            Some(course_id.into()),
+            format!(
+                "Create container ({}) in course ({}): {}",
+                container_id, course_id, name
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
 
-    Ok(rec.0)
+        Ok(u32::try_from(container_id).unwrap())
     }
-    */
 
-    /*
-    async fn get_start_end(pool: &SqlitePool, text_id:u32) -> Result<(u32,u32), GlosserError> {
-    let query = "SELECT b.seq, c.seq FROM assignments a INNER JOIN words b ON a.start = b.word_id INNER JOIN words c ON a.end = c.word_id WHERE a.id = ?;";
+    async fn rename_container(
+        &mut self,
+        container_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "UPDATE containers SET name = $1 WHERE container_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(name)
+            .bind(container_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
 
-    let rec: (u32,u32) = sqlx::query_as(query)
-    .bind(text_id)
-    .fetch_one(pool)
-    .await?;
+        self.update_log_trx(
+            UpdateType::EditContainer,
+            Some(container_id.into()),
+            None,
+            None,
+            format!("Rename container ({}) to {}", container_id, name).as_str(),
+            info,
+        )
+        .await?;
 
-    Ok(rec)
+        Ok(rows)
     }
-    */
-
-    async fn get_glossdb(&mut self, gloss_id: u32) -> Result<GlossEntry, GlosserError> {
-        let query = "SELECT gloss_id, lemma, pos, def, note FROM glosses WHERE gloss_id = $1;";
 
+    async fn delete_container(
+        &mut self,
+        course_id: u32,
+        container_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM container_x_text WHERE course_id = $1 AND container_id = $2;";
         sqlx::query(query)
-            .bind(gloss_id)
+            .bind(course_id)
+            .bind(container_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM course_x_container WHERE course_id = $1 AND container_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(course_id)
+            .bind(container_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "DELETE FROM containers WHERE container_id = $1 \
+        AND NOT EXISTS (SELECT 1 FROM course_x_container WHERE container_id = $1);";
+        sqlx::query(query)
+            .bind(container_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::EditContainer,
+            Some(container_id.into()),
+            None,
+            Some(course_id.into()),
+            format!(
+                "Delete container ({}) from course ({})",
+                container_id, course_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_course_container_order(
+        &mut self,
+        course_id: u32,
+        container_ids: &[u32],
+    ) -> Result<(), GlosserError> {
+        let query = "UPDATE course_x_container SET container_order = $1 WHERE course_id = $2 AND container_id = $3;";
+        for (i, container_id) in container_ids.iter().enumerate() {
+            sqlx::query(query)
+                .bind(i32::try_from(i + 1).unwrap())
+                .bind(course_id)
+                .bind(*container_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+        Ok(())
+    }
+
+    async fn set_text_container(
+        &mut self,
+        course_id: u32,
+        text_ids: &[u32],
+        container_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let mut rows = 0;
+        for text_id in text_ids {
+            let query = "DELETE FROM container_x_text WHERE course_id = $1 AND text_id = $2;";
+            sqlx::query(query)
+                .bind(course_id)
+                .bind(*text_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+
+            if let Some(container_id) = container_id {
+                let query = "INSERT INTO container_x_text (course_id, text_id, container_id) VALUES ($1, $2, $3);";
+                rows += sqlx::query(query)
+                    .bind(course_id)
+                    .bind(*text_id)
+                    .bind(container_id)
+                    .execute(&mut *self.tx)
+                    .await
+                    .map_err(map_sqlx_error)?
+                    .rows_affected();
+            }
+        }
+
+        let text_list = text_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let desc = match container_id {
+            Some(c) => format!(
+                "Put texts ({}) in container ({}) in course ({})",
+                text_list, c, course_id
+            ),
+            None => format!(
+                "Take texts ({}) out of their container in course ({})",
+                text_list, course_id
+            ),
+        };
+        self.update_log_trx(
+            UpdateType::SetTextContainer,
+            text_ids.first().map(|t| (*t).into()),
+            None,
+            Some(course_id.into()),
+            desc.as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+    /*
+    async fn _get_titles(pool: &SqlitePool) -> Result<Vec<(String,u32)>, GlosserError> {
+        let query = "SELECT id,title FROM titles ORDER BY title;";
+        let res: Result<Vec<(String,u32)>, GlosserError> = sqlx::query(query)
+        .map(|rec: SqliteRow| (rec.get("id"),rec.get("title")) )
+        .fetch_all(pool)
+        .await;
+
+        res
+    }
+    */
+    async fn get_text_id_for_word_id(&mut self, word_id: u32) -> Result<u32, GlosserError> {
+        let query = "SELECT text_id FROM words WHERE word_id = $1;";
+
+        let rec: (u32,) = sqlx::query_as(query)
+            .bind(word_id)
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(rec.0)
+    }
+    /*
+    async fn old_get_text_id_for_word_id(pool: &SqlitePool, word_id:u32) -> Result<u32, GlosserError> {
+    let query = "SELECT A.id FROM assignments A INNER JOIN words B ON A.start = B.word_id INNER JOIN words C ON A.end = C.word_id WHERE B.seq <= (SELECT seq FROM words WHERE word_id = ?) AND C.seq >= (SELECT seq FROM words WHERE word_id = ?) LIMIT 1;";
+
+    let rec: (u32,) = sqlx::query_as(query)
+    .bind(word_id)
+    .bind(word_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(rec.0)
+    }
+    */
+
+    /*
+    async fn get_start_end(pool: &SqlitePool, text_id:u32) -> Result<(u32,u32), GlosserError> {
+    let query = "SELECT b.seq, c.seq FROM assignments a INNER JOIN words b ON a.start = b.word_id INNER JOIN words c ON a.end = c.word_id WHERE a.id = ?;";
+
+    let rec: (u32,u32) = sqlx::query_as(query)
+    .bind(text_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(rec)
+    }
+    */
+
+    async fn get_glossdb(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+    ) -> Result<GlossEntry, GlosserError> {
+        let query = "SELECT a.gloss_id, a.lemma, a.pos, a.def, a.note, a.status, a.unit, a.principal_parts, a.genitive, a.gender, a.terminations, \
+        b.def AS course_def, b.note AS course_note \
+        FROM glosses a \
+        LEFT JOIN course_x_gloss b ON (a.gloss_id = b.gloss_id AND b.course_id = $1) \
+        WHERE a.gloss_id = $2;";
+
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(gloss_id)
             .map(|rec: SqliteRow| GlossEntry {
                 hqid: rec.get("gloss_id"),
                 l: rec.get("lemma"),
                 pos: rec.get("pos"),
                 g: rec.get("def"),
                 n: rec.get("note"),
+                parts: gloss_parts_from_row(&rec),
+                course_def: rec.get("course_def"),
+                course_note: rec.get("course_note"),
+                tags: vec![],
+                status: gloss_status_from_row(&rec),
+                unit: rec.get("unit"),
+            })
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn attach_text(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "INSERT INTO course_x_text (course_id, text_id, text_order) \
+        SELECT $1, text_id, (SELECT COALESCE(MAX(text_order), 0) + 1 FROM course_x_text WHERE course_id = $1) \
+        FROM texts WHERE text_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(course_id)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        if rows > 0 {
+            self.update_log_trx(
+                UpdateType::AttachText,
+                Some(text_id.into()),
+                None,
+                Some(course_id.into()),
+                format!("Attach text ({}) to course ({})", text_id, course_id).as_str(),
+                info,
+            )
+            .await?;
+        }
+
+        Ok(rows)
+    }
+
+    async fn detach_text(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM container_x_text WHERE course_id = $1 AND text_id = $2;";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM course_x_text WHERE course_id = $1 AND text_id = $2;";
+        let rows = sqlx::query(query)
+            .bind(course_id)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        self.update_log_trx(
+            UpdateType::DetachText,
+            Some(text_id.into()),
+            None,
+            Some(course_id.into()),
+            format!("Detach text ({}) from course ({})", text_id, course_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_text_arrows(
+        &mut self,
+        course_id: u32,
+        text_id: u32,
+    ) -> Result<Vec<(u32, u32)>, GlosserError> {
+        //H&Q arrows at the placeholder word_id 1 are not tied to a text
+        let query = "SELECT A.gloss_id, A.word_id \
+        FROM arrowed_words A \
+        INNER JOIN words W ON W.word_id = A.word_id \
+        WHERE A.course_id = $1 AND W.text_id = $2 AND A.word_id != 1 \
+        ORDER BY W.seq;";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(text_id)
+            .map(|rec: SqliteRow| (rec.get("gloss_id"), rec.get("word_id")))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_text_word_ids(&mut self, text_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT word_id FROM words WHERE text_id = $1 ORDER BY seq;";
+        sqlx::query(query)
+            .bind(text_id)
+            .map(|rec: SqliteRow| rec.get("word_id"))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_arrows_into_text(
+        &mut self,
+        text_id: u32,
+    ) -> Result<Vec<TextArrowRow>, GlosserError> {
+        let query = "SELECT A.course_id, A.gloss_id, A.word_id \
+        FROM arrowed_words A \
+        INNER JOIN words W ON W.word_id = A.word_id \
+        WHERE W.text_id = $1 AND A.word_id != 1 \
+        ORDER BY A.course_id, W.seq;";
+        sqlx::query(query)
+            .bind(text_id)
+            .map(|rec: SqliteRow| TextArrowRow {
+                course_id: rec.get("course_id"),
+                gloss_id: rec.get("gloss_id"),
+                word_id: rec.get("word_id"),
+                new_word_id: None,
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn delete_text(
+        &mut self,
+        text_id: u32,
+        rearrowed: usize,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT name FROM texts WHERE text_id = $1;";
+        let name: Option<String> = sqlx::query(query)
+            .bind(text_id)
+            .map(|rec: SqliteRow| rec.get("name"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some(name) = name else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO texts_history (text_history_id, text_id, name, parent_id, display, title, updated, user_id) \
+        SELECT NULL, text_id, name, parent_id, display, title, $2, $3 FROM texts WHERE text_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(text_id)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        //rows which hang off the text's words
+        for query in [
+            "DELETE FROM latex_page_breaks WHERE word_id IN (SELECT word_id FROM words WHERE text_id = $1);",
+            "DELETE FROM appcrit WHERE word_id IN (SELECT word_id FROM words WHERE text_id = $1);",
+            "DELETE FROM arrowed_words WHERE word_id IN (SELECT word_id FROM words WHERE text_id = $1);",
+        ] {
+            sqlx::query(query)
+                .bind(text_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        //the words are archived to words_history
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE text_id = $1;";
+        let words = sqlx::query(query)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        for query in [
+            "DELETE FROM words WHERE text_id = $1;",
+            "DELETE FROM container_x_text WHERE text_id = $1;",
+            "DELETE FROM course_x_text WHERE text_id = $1;",
+            "UPDATE course_x_text SET parent_id = NULL WHERE parent_id = $1;",
+            "UPDATE texts SET parent_id = NULL WHERE parent_id = $1;",
+        ] {
+            sqlx::query(query)
+                .bind(text_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        let query = "DELETE FROM texts WHERE text_id = $1;";
+        let rows = sqlx::query(query)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        self.update_log_trx(
+            UpdateType::DeleteText,
+            Some(text_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Delete text ({}) {} and archive its {} words{}",
+                text_id,
+                name,
+                words,
+                if rearrowed > 0 {
+                    format!(", re-arrowed {} glosses", rearrowed)
+                } else {
+                    String::from("")
+                }
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_text_course_ids(&mut self, text_id: u32) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT course_id FROM course_x_text WHERE text_id = $1 ORDER BY course_id;";
+        sqlx::query(query)
+            .bind(text_id)
+            .map(|rec: SqliteRow| rec.get("course_id"))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn split_text(
+        &mut self,
+        text_id: u32,
+        word_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<Option<u32>, GlosserError> {
+        let query = "SELECT seq FROM words WHERE word_id = $1 AND text_id = $2;";
+        let start: Option<i64> = sqlx::query(query)
+            .bind(word_id)
+            .bind(text_id)
+            .map(|rec: SqliteRow| rec.get("seq"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some(start) = start else {
+            return Ok(None);
+        };
+
+        let query = "INSERT INTO texts (text_id, name, parent_id, display, title) \
+        SELECT NULL, $1, parent_id, display, title FROM texts WHERE text_id = $2;";
+        let new_text_id = sqlx::query(query)
+            .bind(name)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE text_id = $1 AND seq >= $2;";
+        sqlx::query(query)
+            .bind(text_id)
+            .bind(start)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query =
+            "UPDATE words SET text_id = $1, seq = seq - $2 + 1 WHERE text_id = $3 AND seq >= $2;";
+        let moved = sqlx::query(query)
+            .bind(new_text_id)
+            .bind(start)
+            .bind(text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        //the new text takes the old one's place in each course and container, to be reordered
+        for query in [
+            "INSERT INTO course_x_text (course_id, text_id, text_order, parent_id) \
+            SELECT course_id, $1, text_order, parent_id FROM course_x_text WHERE text_id = $2;",
+            "INSERT INTO container_x_text (course_id, text_id, container_id) \
+            SELECT course_id, $1, container_id FROM container_x_text WHERE text_id = $2;",
+        ] {
+            sqlx::query(query)
+                .bind(new_text_id)
+                .bind(text_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.update_log_trx(
+            UpdateType::SplitText,
+            Some(text_id.into()),
+            None,
+            None,
+            format!(
+                "Split text ({}) at word ({}), moving {} words to new text ({})",
+                text_id, word_id, moved, new_text_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(Some(u32::try_from(new_text_id).unwrap()))
+    }
+
+    async fn merge_texts(
+        &mut self,
+        text_id: u32,
+        next_text_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT COALESCE(MAX(seq), 0) AS last_seq FROM words WHERE text_id = $1;";
+        let last_seq: i64 = sqlx::query(query)
+            .bind(text_id)
+            .map(|rec: SqliteRow| rec.get("last_seq"))
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "INSERT INTO texts_history (text_history_id, text_id, name, parent_id, display, title, updated, user_id) \
+        SELECT NULL, text_id, name, parent_id, display, title, $2, $3 FROM texts WHERE text_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(next_text_id)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        let query = "INSERT INTO words_history \
+        (word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE text_id = $1;";
+        sqlx::query(query)
+            .bind(next_text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "UPDATE words SET text_id = $1, seq = seq + $2 WHERE text_id = $3;";
+        let moved = sqlx::query(query)
+            .bind(text_id)
+            .bind(last_seq)
+            .bind(next_text_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        for query in [
+            "DELETE FROM container_x_text WHERE text_id = $1;",
+            "DELETE FROM course_x_text WHERE text_id = $1;",
+            "UPDATE course_x_text SET parent_id = NULL WHERE parent_id = $1;",
+            "UPDATE texts SET parent_id = NULL WHERE parent_id = $1;",
+            "DELETE FROM texts WHERE text_id = $1;",
+        ] {
+            sqlx::query(query)
+                .bind(next_text_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        self.update_log_trx(
+            UpdateType::MergeTexts,
+            Some(text_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Merge text ({}) into text ({}), moving {} words",
+                next_text_id, text_id, moved
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(moved)
+    }
+
+    async fn set_course_gloss(
+        &mut self,
+        course_id: u32,
+        gloss_id: u32,
+        def: Option<&str>,
+        note: Option<&str>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let res = if def.is_none() && note.is_none() {
+            let query = "DELETE FROM course_x_gloss WHERE course_id = $1 AND gloss_id = $2;";
+            sqlx::query(query)
+                .bind(course_id)
+                .bind(gloss_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+        } else {
+            let query = "REPLACE INTO course_x_gloss (course_id, gloss_id, def, note, updated, user_id) \
+            VALUES ($1, $2, $3, $4, $5, $6);";
+            sqlx::query(query)
+                .bind(course_id)
+                .bind(gloss_id)
+                .bind(def)
+                .bind(note)
+                .bind(info.timestamp)
+                .bind(info.user_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+        };
+
+        self.update_log_trx(
+            UpdateType::EditCourseGloss,
+            Some(gloss_id.into()),
+            None,
+            Some(course_id.into()),
+            format!(
+                "{} for gloss ({}) in course ({})",
+                match (def, note) {
+                    (Some(_), Some(_)) => "Set course definition and note",
+                    (Some(_), None) => "Set course definition",
+                    (None, Some(_)) => "Set course note",
+                    (None, None) => "Removed course definition and note",
+                },
+                gloss_id,
+                course_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_tags(&mut self) -> Result<Vec<Tag>, GlosserError> {
+        let query = "SELECT tag_id, name FROM tags ORDER BY name;";
+        sqlx::query(query)
+            .map(|rec: SqliteRow| Tag {
+                tag_id: rec.get("tag_id"),
+                name: rec.get("name"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn insert_tag(&mut self, name: &str, info: &ConnectionInfo) -> Result<i64, GlosserError> {
+        let query = "INSERT INTO tags (tag_id, name) VALUES (NULL, $1);";
+        let tag_id = sqlx::query(query)
+            .bind(name)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+
+        self.update_log_trx(
+            UpdateType::EditTag,
+            Some(tag_id),
+            None,
+            None,
+            format!("Added tag ({}) {}", tag_id, name).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(tag_id)
+    }
+
+    async fn update_tag(
+        &mut self,
+        tag_id: u32,
+        name: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "UPDATE tags SET name = $1 WHERE tag_id = $2;";
+        let res = sqlx::query(query)
+            .bind(name)
+            .bind(tag_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::EditTag,
+            Some(tag_id.into()),
+            None,
+            None,
+            format!("Renamed tag ({}) to {}", tag_id, name).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn delete_tag(
+        &mut self,
+        tag_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM gloss_x_tag WHERE tag_id = $1;";
+        sqlx::query(query)
+            .bind(tag_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "DELETE FROM tags WHERE tag_id = $1;";
+        let res = sqlx::query(query)
+            .bind(tag_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::EditTag,
+            Some(tag_id.into()),
+            None,
+            None,
+            format!("Deleted tag ({})", tag_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn tag_gloss(
+        &mut self,
+        gloss_id: u32,
+        tag_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "INSERT OR IGNORE INTO gloss_x_tag (gloss_id, tag_id) VALUES ($1, $2);";
+        let res = sqlx::query(query)
+            .bind(gloss_id)
+            .bind(tag_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::TagGloss,
+            Some(gloss_id.into()),
+            None,
+            None,
+            format!("Added tag ({}) to gloss ({})", tag_id, gloss_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn untag_gloss(
+        &mut self,
+        gloss_id: u32,
+        tag_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "DELETE FROM gloss_x_tag WHERE gloss_id = $1 AND tag_id = $2;";
+        let res = sqlx::query(query)
+            .bind(gloss_id)
+            .bind(tag_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::TagGloss,
+            Some(gloss_id.into()),
+            None,
+            None,
+            format!("Removed tag ({}) from gloss ({})", tag_id, gloss_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_gloss_tags(&mut self, gloss_id: u32) -> Result<Vec<Tag>, GlosserError> {
+        let query = "SELECT b.tag_id, b.name FROM gloss_x_tag a \
+        INNER JOIN tags b ON a.tag_id = b.tag_id \
+        WHERE a.gloss_id = $1 ORDER BY b.name;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| Tag {
+                tag_id: rec.get("tag_id"),
+                name: rec.get("name"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_tagged_gloss_ids(&mut self, tag_ids: &[u32]) -> Result<Vec<u32>, GlosserError> {
+        if tag_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let query = format!(
+            "SELECT DISTINCT gloss_id FROM gloss_x_tag WHERE tag_id IN ({});",
+            tag_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        sqlx::query(&query)
+            .map(|rec: SqliteRow| rec.get("gloss_id"))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn set_gloss_status(
+        &mut self,
+        gloss_id: u32,
+        status: GlossStatus,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT status FROM glosses WHERE gloss_id = $1;";
+        let old_status = sqlx::query(query)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| gloss_status_from_row(&rec))
+            .fetch_one(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        if old_status == status {
+            return Ok(0);
+        }
+
+        let query = "INSERT INTO glosses_history \
+        (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT NULL, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations FROM glosses WHERE gloss_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+
+        self.update_log_trx(
+            UpdateType::SetGlossStatus,
+            Some(gloss_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Changed gloss ({}) from {} to {}",
+                gloss_id,
+                old_status.name(),
+                status.name()
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query =
+            "UPDATE glosses SET status = $1, updated = $2, updatedUser = $3 WHERE gloss_id = $4;";
+        let res = sqlx::query(query)
+            .bind(status.value())
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_glosses_by_status(
+        &mut self,
+        status: GlossStatus,
+    ) -> Result<Vec<GlossReviewRow>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, pos, def, status FROM glosses \
+        WHERE status = $1 ORDER BY sortalpha, sortkey;";
+        sqlx::query(query)
+            .bind(status.value())
+            .map(|rec: SqliteRow| gloss_review_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_unapproved_glosses(
+        &mut self,
+        text_ids: &[u32],
+    ) -> Result<Vec<GlossReviewRow>, GlosserError> {
+        if text_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let query = format!(
+            "SELECT DISTINCT b.gloss_id, b.lemma, b.pos, b.def, b.status, b.sortalpha, b.sortkey FROM words a \
+            INNER JOIN glosses b ON a.gloss_id = b.gloss_id \
+            WHERE a.text_id IN ({}) AND b.status != $1 ORDER BY b.sortalpha, b.sortkey;",
+            text_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        sqlx::query(&query)
+            .bind(GlossStatus::Approved.value())
+            .map(|rec: SqliteRow| gloss_review_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_text_gloss_counts(
+        &mut self,
+        course_id: u32,
+    ) -> Result<Vec<TextGlossCount>, GlosserError> {
+        let query = "SELECT B.text_id, T.name, B.text_order, A.gloss_id, G.unit, COUNT(A.word_id) AS tokens, \
+        F.text_order AS arrowed_text_order, COALESCE(D.word_id = 1, FALSE) AS arrowed_hq \
+        FROM course_x_text B \
+        INNER JOIN texts T ON B.text_id = T.text_id \
+        LEFT JOIN words A ON A.text_id = B.text_id AND A.type = 0 \
+        LEFT JOIN glosses G ON A.gloss_id = G.gloss_id \
+        LEFT JOIN arrowed_words D ON A.gloss_id = D.gloss_id AND D.course_id = $1 \
+        LEFT JOIN words E ON E.word_id = D.word_id \
+        LEFT JOIN course_x_text F ON E.text_id = F.text_id AND F.course_id = $1 \
+        WHERE B.course_id = $1 \
+        GROUP BY B.text_id, T.name, B.text_order, A.gloss_id, G.unit, F.text_order, D.word_id \
+        ORDER BY B.text_order, A.gloss_id;";
+        sqlx::query(query)
+            .bind(course_id)
+            .map(|rec: SqliteRow| TextGlossCount {
+                text_id: rec.get("text_id"),
+                text_name: rec.get("name"),
+                text_order: rec.get("text_order"),
+                gloss_id: rec.get("gloss_id"),
+                unit: rec.get("unit"),
+                tokens: u32::try_from(rec.get::<i64, _>("tokens")).unwrap(),
+                arrowed_text_order: rec.get("arrowed_text_order"),
+                arrowed_hq: rec.get("arrowed_hq"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_glossary(
+        &mut self,
+        course_id: Option<u32>,
+    ) -> Result<Vec<GlossaryRow>, GlosserError> {
+        let (course_join, course_filter) = if course_id.is_some() {
+            (
+                "LEFT JOIN course_x_gloss cg ON (g.gloss_id = cg.gloss_id AND cg.course_id = $1)",
+                "AND g.gloss_id IN (SELECT a.gloss_id FROM words a \
+                INNER JOIN course_x_text b ON a.text_id = b.text_id WHERE b.course_id = $1)",
+            )
+        } else {
+            //no course, so the join matches nothing and the glosses' own def and note are used
+            (
+                "LEFT JOIN course_x_gloss cg ON (g.gloss_id = cg.gloss_id AND cg.course_id IS NULL)",
+                "",
+            )
+        };
+        let query = format!(
+            "SELECT g.gloss_id, g.lemma, g.sortalpha, g.pos, COALESCE(cg.def, g.def) AS def, COALESCE(cg.note, g.note) AS note, \
+            g.unit, g.status, g.principal_parts, g.genitive, g.gender, g.terminations \
+            FROM glosses g {} WHERE g.status > 0 {} ORDER BY g.sortalpha, g.sortkey, g.gloss_id;",
+            course_join, course_filter
+        );
+        let mut sql = sqlx::query(&query);
+        if let Some(course_id) = course_id {
+            sql = sql.bind(course_id);
+        }
+        sql.map(|rec: SqliteRow| glossary_row(&rec))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_deleted_glosses(&mut self) -> Result<Vec<DeletedGlossRow>, GlosserError> {
+        let query = "SELECT a.gloss_id, a.lemma, a.pos, a.def, b.updated AS deleted_at, c.initials AS deleted_by \
+        FROM glosses a \
+        LEFT JOIN update_log b ON b.update_id = \
+            (SELECT MAX(update_id) FROM update_log WHERE update_type = $1 AND object_id = a.gloss_id) \
+        LEFT JOIN users c ON b.user_id = c.user_id \
+        WHERE a.status = 0 ORDER BY a.sortalpha, a.sortkey;";
+        sqlx::query(query)
+            .bind(UpdateType::DeleteGloss.value())
+            .map(|rec: SqliteRow| DeletedGlossRow {
+                gloss_id: rec.get("gloss_id"),
+                lemma: rec.get("lemma"),
+                pos: rec.get("pos"),
+                def: rec.get("def"),
+                deleted_by: rec.get("deleted_by"),
+                deleted_at: rec.get("deleted_at"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn get_status_before_delete(
+        &mut self,
+        gloss_id: u32,
+    ) -> Result<Option<GlossStatus>, GlosserError> {
+        let query = "SELECT status FROM glosses_history WHERE gloss_id = $1 AND status > 0 \
+        ORDER BY gloss_history_id DESC LIMIT 1;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| gloss_status_from_row(&rec))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn replace_gloss(
+        &mut self,
+        gloss_id: u32,
+        replacement_id: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<(u64, u64, u64, u64), GlosserError> {
+        let query = "INSERT INTO arrowed_words_history (history_id, course_id, gloss_id, word_id, updated, user_id, comment) \
+        SELECT NULL, course_id, gloss_id, word_id, updated, user_id, comment FROM arrowed_words WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        //an arrow moves only if the replacement is not already arrowed in that course
+        let mut arrows_moved = 0;
+        if replacement_id.is_some() {
+            let query = "UPDATE arrowed_words SET gloss_id = $2, updated = $3, user_id = $4 \
+            WHERE gloss_id = $1 AND course_id NOT IN (SELECT course_id FROM arrowed_words WHERE gloss_id = $2);";
+            arrows_moved = sqlx::query(query)
+                .bind(gloss_id)
+                .bind(replacement_id)
+                .bind(info.timestamp)
+                .bind(info.user_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected();
+        }
+        let query = "DELETE FROM arrowed_words WHERE gloss_id = $1;";
+        let arrows_removed = sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let query = "INSERT INTO words_history \
+        (word_history_id, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT NULL, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let query =
+            "UPDATE words SET gloss_id = $2, updated = $3, updatedUser = $4 WHERE gloss_id = $1;";
+        let words = sqlx::query(query)
+            .bind(gloss_id)
+            .bind(replacement_id)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+
+        let forms = if replacement_id.is_some() {
+            let query = "UPDATE lemmatizer SET gloss_id = $2 WHERE gloss_id = $1;";
+            sqlx::query(query)
+                .bind(gloss_id)
+                .bind(replacement_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected()
+        } else {
+            let query = "DELETE FROM lemmatizer WHERE gloss_id = $1;";
+            sqlx::query(query)
+                .bind(gloss_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?
+                .rows_affected()
+        };
+
+        let query = "INSERT INTO glosses_history \
+        (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT NULL, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations FROM glosses WHERE gloss_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+
+        let query =
+            "UPDATE glosses SET status = 0, updated = $2, updatedUser = $3 WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .bind(info.timestamp)
+            .bind(info.user_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let target = match replacement_id {
+            Some(r) => format!("gloss ({})", r),
+            None => String::from("nothing"),
+        };
+        self.update_log_trx(
+            UpdateType::DeleteGloss,
+            Some(gloss_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Deleted gloss ({}), replacing it with {}: {} words, {} lemmatizer forms, {} arrows moved, {} arrows removed",
+                gloss_id, target, words, forms, arrows_moved, arrows_removed
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok((words, forms, arrows_moved, arrows_removed))
+    }
+
+    async fn purge_glosses(&mut self, info: &ConnectionInfo) -> Result<Vec<u32>, GlosserError> {
+        let query = "SELECT gloss_id, lemma FROM glosses a WHERE status = 0 \
+        AND NOT EXISTS (SELECT 1 FROM words WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM words_history WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM arrowed_words WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM arrowed_words_history WHERE gloss_id = a.gloss_id) \
+        AND NOT EXISTS (SELECT 1 FROM glosses_history h WHERE h.gloss_id = a.gloss_id \
+            AND h.gloss_history_id NOT IN (SELECT history_id FROM update_log \
+            WHERE update_type = $1 AND object_id = a.gloss_id AND history_id IS NOT NULL)) \
+        ORDER BY gloss_id;";
+        let glosses: Vec<(u32, String)> = sqlx::query(query)
+            .bind(UpdateType::DeleteGloss.value())
+            .map(|rec: SqliteRow| (rec.get("gloss_id"), rec.get("lemma")))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        for (gloss_id, lemma) in &glosses {
+            for query in [
+                "DELETE FROM gloss_x_tag WHERE gloss_id = $1;",
+                "DELETE FROM course_x_gloss WHERE gloss_id = $1;",
+                "DELETE FROM course_known_glosses WHERE gloss_id = $1;",
+                "DELETE FROM lemmatizer WHERE gloss_id = $1;",
+                "DELETE FROM glosses_history WHERE gloss_id = $1;",
+                "DELETE FROM glosses WHERE gloss_id = $1;",
+            ] {
+                sqlx::query(query)
+                    .bind(*gloss_id)
+                    .execute(&mut *self.tx)
+                    .await
+                    .map_err(map_sqlx_error)?;
+            }
+
+            self.update_log_trx(
+                UpdateType::PurgeGloss,
+                Some((*gloss_id).into()),
+                None,
+                None,
+                format!("Purged gloss ({}) {}", gloss_id, lemma).as_str(),
+                info,
+            )
+            .await?;
+        }
+
+        Ok(glosses.into_iter().map(|g| g.0).collect())
+    }
+
+    async fn get_sortalphas(&mut self) -> Result<Vec<(u32, String, String, String)>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, sortalpha, sortkey FROM glosses ORDER BY gloss_id;";
+        sqlx::query(query)
+            .map(|rec: SqliteRow| {
+                (
+                    rec.get("gloss_id"),
+                    rec.get("lemma"),
+                    rec.get("sortalpha"),
+                    rec.get("sortkey"),
+                )
             })
-            .fetch_one(&mut *self.tx)
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn set_sortalpha(
+        &mut self,
+        gloss_id: u32,
+        sortalpha: &str,
+        sortkey: &str,
+    ) -> Result<u64, GlosserError> {
+        let query = "UPDATE glosses SET sortalpha = $1, sortkey = $3 WHERE gloss_id = $2;";
+        let res = sqlx::query(query)
+            .bind(sortalpha)
+            .bind(gloss_id)
+            .bind(sortkey)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        Ok(res.rows_affected())
+    }
+
+    async fn get_glossary_row(
+        &mut self,
+        gloss_id: u32,
+    ) -> Result<Option<GlossaryRow>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, sortalpha, pos, def, note, unit, status, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .map(|rec: SqliteRow| glossary_row(&rec))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
+
+    async fn find_glossary_rows(
+        &mut self,
+        lemma: &str,
+        sortalpha: &str,
+    ) -> Result<Vec<GlossaryRow>, GlosserError> {
+        let query = "SELECT gloss_id, lemma, sortalpha, pos, def, note, unit, status, principal_parts, genitive, gender, terminations \
+        FROM glosses WHERE lemma = $1 AND sortalpha = $2 AND status > 0;";
+        sqlx::query(query)
+            .bind(lemma)
+            .bind(sortalpha)
+            .map(|rec: SqliteRow| glossary_row(&rec))
+            .fetch_all(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)
     }
 
+    async fn insert_glossary_row(
+        &mut self,
+        row: &GlossaryRow,
+        info: &ConnectionInfo,
+    ) -> Result<u32, GlosserError> {
+        let query = "INSERT INTO glosses (gloss_id, unit, lemma, sortalpha, \
+        def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations, sortkey) \
+        VALUES (NULL, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14);";
+        let gloss_id = sqlx::query(query)
+            .bind(row.unit.unwrap_or(0))
+            .bind(&row.lemma)
+            .bind(&row.sortalpha)
+            .bind(&row.def)
+            .bind(&row.pos)
+            .bind(&row.note)
+            .bind(info.timestamp)
+            .bind(row.status.unwrap_or(GlossStatus::Draft).value())
+            .bind(info.user_id)
+            .bind(&row.principal_parts)
+            .bind(&row.genitive)
+            .bind(&row.gender)
+            .bind(&row.terminations)
+            .bind(sort_key(&row.lemma))
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+
+        Ok(u32::try_from(gloss_id).unwrap())
+    }
+
+    async fn update_glossary_row(
+        &mut self,
+        gloss_id: u32,
+        row: &GlossaryRow,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "INSERT INTO glosses_history \
+        (gloss_history_id, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations) \
+        SELECT NULL, gloss_id, unit, lemma, sortalpha, def, pos, note, updated, status, updatedUser, principal_parts, genitive, gender, terminations FROM glosses WHERE gloss_id = $1;";
+        sqlx::query(query)
+            .bind(gloss_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        let query = "UPDATE glosses SET \
+        unit = $1, \
+        lemma = $2, \
+        sortalpha = $3, \
+        sortkey = $15, \
+        def = $4, \
+        pos = $5, \
+        note = $6, \
+        updated = $7, \
+        status = $8, \
+        updatedUser = $9, \
+        principal_parts = $10, \
+        genitive = $11, \
+        gender = $12, \
+        terminations = $13 \
+        WHERE gloss_id = $14;";
+        let res = sqlx::query(query)
+            .bind(row.unit.unwrap_or(0))
+            .bind(&row.lemma)
+            .bind(&row.sortalpha)
+            .bind(&row.def)
+            .bind(&row.pos)
+            .bind(&row.note)
+            .bind(info.timestamp)
+            .bind(row.status.unwrap_or(GlossStatus::Approved).value())
+            .bind(info.user_id)
+            .bind(&row.principal_parts)
+            .bind(&row.genitive)
+            .bind(&row.gender)
+            .bind(&row.terminations)
+            .bind(gloss_id)
+            .bind(sort_key(&row.lemma))
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
     //SELECT c.name, a.word_id, a.word, d.word_id as arrowed FROM words a INNER JOIN course_x_text b ON (a.text = b.text_id AND b.course_id = 1) INNER JOIN texts c ON a.text = c.text_id LEFT JOIN arrowed_words d ON (d.course_id=1 AND d.gloss_id=564 AND d.word_id = a.word_id) WHERE a.gloss_id = 564 ORDER BY b.text_order, a.seq LIMIT 20000;
 
     async fn get_gloss_occurrences(
@@ -1509,15 +3452,25 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         page: i32,
         limit: u32,
         course_id: u32,
+        regex: Option<&GlossRegex>,
+        tags: &TagFilter,
     ) -> Result<Vec<(String, u32, String, u32)>, GlosserError> {
+        let regex_filter = match regex {
+            Some(r) => format!("AND a.{} REGEXP $1", r.column()),
+            None => String::from(""),
+        };
         let query = format!("WITH gloss_total AS (
             SELECT gloss_id, COUNT(gloss_id) AS total_count
             FROM words a2
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {}
             GROUP BY gloss_id
         )
-        SELECT a.gloss_id, a.lemma, a.def, b.total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id WHERE a.sortalpha COLLATE PolytonicGreek < '{}' AND status > 0 AND pos != 'gloss' ORDER BY a.sortalpha COLLATE PolytonicGreek DESC LIMIT {}, {};", course_id, searchprefix, -page * limit as i32, limit);
-        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sqlx::query(&query)
+        SELECT a.gloss_id, a.lemma, COALESCE(cg.def, a.def) AS def, b.total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {}) WHERE a.sortalpha < '{}' AND status > 0 AND pos != 'gloss' {}{} ORDER BY a.sortalpha DESC, a.sortkey DESC, a.gloss_id DESC LIMIT {}, {};", course_id, course_id, searchprefix, regex_filter, tags.sql("a.gloss_id"), -page * limit as i32, limit);
+        let mut sql = sqlx::query(&query);
+        if let Some(r) = regex {
+            sql = sql.bind(&r.pattern);
+        }
+        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sql
             .map(|rec: SqliteRow| {
                 (
                     rec.get("lemma"),
@@ -1539,16 +3492,26 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         page: i32,
         limit: u32,
         course_id: u32,
+        regex: Option<&GlossRegex>,
+        tags: &TagFilter,
     ) -> Result<Vec<(String, u32, String, u32)>, GlosserError> {
+        let regex_filter = match regex {
+            Some(r) => format!("AND a.{} REGEXP $1", r.column()),
+            None => String::from(""),
+        };
         let query = format!("WITH gloss_total AS (
             SELECT gloss_id, COUNT(gloss_id) AS total_count
             FROM words a2
             INNER JOIN course_x_text b2 ON a2.text_id = b2.text_id AND course_id = {}
             GROUP BY gloss_id
         )
-        SELECT a.gloss_id, a.lemma, a.def, b.total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id WHERE a.sortalpha COLLATE PolytonicGreek >= '{}' AND status > 0 AND pos != 'gloss' ORDER BY a.sortalpha COLLATE PolytonicGreek LIMIT {}, {};",
-        course_id, searchprefix, page * limit as i32, limit);
-        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sqlx::query(&query)
+        SELECT a.gloss_id, a.lemma, COALESCE(cg.def, a.def) AS def, b.total_count FROM glosses a LEFT JOIN gloss_total b ON a.gloss_id = b.gloss_id LEFT JOIN course_x_gloss cg ON (a.gloss_id = cg.gloss_id AND cg.course_id = {}) WHERE a.sortalpha >= '{}' AND status > 0 AND pos != 'gloss' {}{} ORDER BY a.sortalpha, a.sortkey, a.gloss_id LIMIT {}, {};",
+        course_id, course_id, searchprefix, regex_filter, tags.sql("a.gloss_id"), page * limit as i32, limit);
+        let mut sql = sqlx::query(&query);
+        if let Some(r) = regex {
+            sql = sql.bind(&r.pattern);
+        }
+        let res: Result<Vec<(String, u32, String, u32)>, GlosserError> = sql
             .map(|rec: SqliteRow| {
                 (
                     rec.get("lemma"),
@@ -1613,50 +3576,302 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
         before_word_id: u32,
         word_type: u32,
         word: &str,
-    ) -> Result<i64, GlosserError> {
-        let query = r#"UPDATE words set seq = seq + 1 WHERE text_id > 269 AND text_id < 296 AND seq >= (SELECT seq FROM words WHERE word_id = $1);"#;
-        let _res = sqlx::query(query)
+        info: &ConnectionInfo,
+    ) -> Result<Option<u32>, GlosserError> {
+        let query = "SELECT seq, text_id FROM words WHERE word_id = $1;";
+        let before: Option<(i64, u32)> = sqlx::query(query)
             .bind(before_word_id)
+            .map(|rec: SqliteRow| (rec.get("seq"), rec.get("text_id")))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((seq, text_id)) = before else {
+            return Ok(None);
+        };
+
+        let query = "UPDATE words SET seq = seq + 1 WHERE text_id = $1 AND seq >= $2;";
+        sqlx::query(query)
+            .bind(text_id)
+            .bind(seq)
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?;
 
         //do not insert value for updated so it gets default timestamp, inserting null to it does not set the timestamp
-        let query2 = r#"INSERT INTO words (word_id, seq, text_id, word, gloss_id, type, updatedUser, isFlagged, note)
-            VALUES (NULL,
-                (SELECT seq - 1 FROM words WHERE word_id = $1),
-                (SELECT text_id FROM words WHERE word_id = $2),
-                $3, NULL, $4, '', 0, '');"#;
-        let word_id = sqlx::query(query2)
-            .bind(before_word_id)
-            .bind(before_word_id)
-            .bind(word) //''
-            .bind(word_type) //6
+        let query = "INSERT INTO words (word_id, seq, text_id, word, gloss_id, type, updatedUser, isFlagged, note) \
+        VALUES (NULL, $1, $2, $3, NULL, $4, '', 0, '');";
+        let word_id = sqlx::query(query)
+            .bind(seq)
+            .bind(text_id)
+            .bind(word)
+            .bind(word_type)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+
+        self.update_log_trx(
+            UpdateType::InsertWord,
+            Some(word_id),
+            None,
+            None,
+            format!(
+                "Insert word ({}) {} of type {} before word ({}) in text ({})",
+                word_id, word, word_type, before_word_id, text_id
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(Some(u32::try_from(word_id).unwrap()))
+    }
+
+    async fn update_word(
+        &mut self,
+        word_id: u32,
+        word: Option<&str>,
+        word_type: Option<u32>,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT word, COALESCE(type, 0) AS type FROM words WHERE word_id = $1;";
+        let old: Option<(String, u32)> = sqlx::query(query)
+            .bind(word_id)
+            .map(|rec: SqliteRow| (rec.get("word"), rec.get("type")))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((old_word, old_type)) = old else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO words_history \
+        (word_history_id, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT NULL, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE word_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(word_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        self.update_log_trx(
+            UpdateType::EditWord,
+            Some(word_id.into()),
+            Some(history_id),
+            None,
+            format!(
+                "Edit word ({}) from {} of type {} to {} of type {}",
+                word_id,
+                old_word,
+                old_type,
+                word.unwrap_or(&old_word),
+                word_type.unwrap_or(old_type)
+            )
+            .as_str(),
+            info,
+        )
+        .await?;
+
+        let query = "UPDATE words SET word = COALESCE($1, word), type = COALESCE($2, type) WHERE word_id = $3;";
+        let res = sqlx::query(query)
+            .bind(word)
+            .bind(word_type)
+            .bind(word_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn delete_word(
+        &mut self,
+        word_id: u32,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT word, seq, text_id FROM words WHERE word_id = $1;";
+        let old: Option<(String, i64, u32)> = sqlx::query(query)
+            .bind(word_id)
+            .map(|rec: SqliteRow| (rec.get("word"), rec.get("seq"), rec.get("text_id")))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some((word, seq, text_id)) = old else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO words_history \
+        (word_history_id, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT NULL, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE word_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(word_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .last_insert_rowid();
+        for query in [
+            "DELETE FROM latex_page_breaks WHERE word_id = $1;",
+            "DELETE FROM appcrit WHERE word_id = $1;",
+            "DELETE FROM arrowed_words WHERE word_id = $1;",
+        ] {
+            sqlx::query(query)
+                .bind(word_id)
+                .execute(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+        }
+
+        let query = "DELETE FROM words WHERE word_id = $1;";
+        let rows = sqlx::query(query)
+            .bind(word_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?
+            .rows_affected();
+        let query = "UPDATE words SET seq = seq - 1 WHERE text_id = $1 AND seq > $2;";
+        sqlx::query(query)
+            .bind(text_id)
+            .bind(seq)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        self.update_log_trx(
+            UpdateType::DeleteWord,
+            Some(word_id.into()),
+            Some(history_id),
+            None,
+            format!("Delete word ({}) {} from text ({})", word_id, word, text_id).as_str(),
+            info,
+        )
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_word_flag(
+        &mut self,
+        word_id: u32,
+        flagged: bool,
+        note: &str,
+        info: &ConnectionInfo,
+    ) -> Result<u64, GlosserError> {
+        let query = "SELECT word FROM words WHERE word_id = $1;";
+        let word: Option<String> = sqlx::query(query)
+            .bind(word_id)
+            .map(|rec: SqliteRow| rec.get("word"))
+            .fetch_optional(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+        let Some(word) = word else {
+            return Ok(0);
+        };
+
+        let query = "INSERT INTO words_history \
+        (word_history_id, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note) \
+        SELECT NULL, word_id, seq, text_id, word, gloss_id, type, updated, updatedUser, isFlagged, note FROM words \
+        WHERE word_id = $1;";
+        let history_id = sqlx::query(query)
+            .bind(word_id)
             .execute(&mut *self.tx)
             .await
             .map_err(map_sqlx_error)?
             .last_insert_rowid();
+        let (update_type, desc) = if flagged {
+            (UpdateType::FlagWord, "Flag")
+        } else {
+            (UpdateType::UnflagWord, "Unflag")
+        };
+        self.update_log_trx(
+            update_type,
+            Some(word_id.into()),
+            Some(history_id),
+            None,
+            format!("{} word ({}) {}: {}", desc, word_id, word, note).as_str(),
+            info,
+        )
+        .await?;
+
+        let query = "UPDATE words SET isFlagged = $1, note = $2 WHERE word_id = $3;";
+        let res = sqlx::query(query)
+            .bind(i32::from(flagged))
+            .bind(note)
+            .bind(word_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)?;
+
+        Ok(res.rows_affected())
+    }
+
+    async fn get_flagged_words(
+        &mut self,
+        course_id: u32,
+        text_id: Option<u32>,
+    ) -> Result<Vec<FlaggedWordRow>, GlosserError> {
+        let query = "SELECT A.word_id, A.text_id, T.name, A.seq, A.word, A.note, \
+        (SELECT COALESCE(GROUP_CONCAT(W.word, ' ' ORDER BY W.seq), '') FROM words W \
+        WHERE W.text_id = A.text_id AND W.seq BETWEEN A.seq - 3 AND A.seq + 3 AND W.word <> '') AS context \
+        FROM words A \
+        INNER JOIN texts T ON A.text_id = T.text_id \
+        INNER JOIN course_x_text B ON A.text_id = B.text_id AND B.course_id = $1 \
+        WHERE A.isFlagged = 1 AND ($2 IS NULL OR A.text_id = $2) \
+        ORDER BY B.text_order, A.seq;";
+        sqlx::query(query)
+            .bind(course_id)
+            .bind(text_id)
+            .map(|rec: SqliteRow| FlaggedWordRow {
+                word_id: rec.get("word_id"),
+                text_id: rec.get("text_id"),
+                text_name: rec.get("name"),
+                seq: rec.get("seq"),
+                word: rec.get("word"),
+                note: rec.get("note"),
+                context: rec.get("context"),
+            })
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
+    }
 
-        Ok(word_id)
+    async fn get_word_arrows(&mut self, word_id: u32) -> Result<Vec<(u32, u32)>, GlosserError> {
+        let query =
+            "SELECT course_id, gloss_id FROM arrowed_words WHERE word_id = $1 ORDER BY course_id;";
+        sqlx::query(query)
+            .bind(word_id)
+            .map(|rec: SqliteRow| (rec.get("course_id"), rec.get("gloss_id")))
+            .fetch_all(&mut *self.tx)
+            .await
+            .map_err(map_sqlx_error)
     }
 
     async fn create_db(&mut self) -> Result<(), GlosserError> {
         let query = r#"
             CREATE TABLE IF NOT EXISTS courses (course_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, name TEXT NOT NULL) STRICT;
-            CREATE TABLE IF NOT EXISTS course_x_text (course_id INTEGER NOT NULL REFERENCES courses (course_id), text_id INTEGER NOT NULL REFERENCES texts (text_id), text_order INTEGER NOT NULL, PRIMARY KEY (course_id, text_id)) STRICT;
-            CREATE TABLE IF NOT EXISTS glosses (gloss_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '') STRICT;
+            CREATE TABLE IF NOT EXISTS course_x_text (course_id INTEGER NOT NULL REFERENCES courses (course_id), text_id INTEGER NOT NULL REFERENCES texts (text_id), text_order INTEGER NOT NULL, parent_id INTEGER, PRIMARY KEY (course_id, text_id)) STRICT;
+            CREATE TABLE IF NOT EXISTS glosses (gloss_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '', principal_parts TEXT, genitive TEXT, gender TEXT, terminations TEXT, sortkey TEXT NOT NULL DEFAULT '') STRICT;
+            CREATE TABLE IF NOT EXISTS course_x_gloss (course_id INTEGER NOT NULL REFERENCES courses (course_id), gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), def TEXT, note TEXT, updated INTEGER, user_id INTEGER REFERENCES users (user_id), PRIMARY KEY (course_id, gloss_id)) STRICT;
+            CREATE TABLE IF NOT EXISTS tags (tag_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, name TEXT NOT NULL UNIQUE) STRICT;
+            CREATE TABLE IF NOT EXISTS gloss_x_tag (gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), tag_id INTEGER NOT NULL REFERENCES tags (tag_id), PRIMARY KEY (gloss_id, tag_id)) STRICT;
+            CREATE TABLE IF NOT EXISTS course_baselines (course_id INTEGER NOT NULL PRIMARY KEY REFERENCES courses (course_id), max_unit INTEGER NOT NULL DEFAULT 0) STRICT;
+            CREATE TABLE IF NOT EXISTS course_known_glosses (course_id INTEGER NOT NULL REFERENCES courses (course_id), gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), PRIMARY KEY (course_id, gloss_id)) STRICT;
             CREATE TABLE IF NOT EXISTS arrowed_words (course_id INTEGER NOT NULL REFERENCES courses (course_id), gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), word_id INTEGER NOT NULL REFERENCES words (word_id), updated INTEGER, user_id INTEGER REFERENCES users (user_id), comment TEXT, PRIMARY KEY (course_id, gloss_id, word_id)) STRICT;
             CREATE TABLE IF NOT EXISTS arrowed_words_history (history_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, course_id INTEGER NOT NULL REFERENCES courses (course_id), gloss_id INTEGER NOT NULL REFERENCES glosses (gloss_id), word_id INTEGER, updated INTEGER, user_id INTEGER REFERENCES users (user_id), comment TEXT) STRICT;
             CREATE TABLE IF NOT EXISTS appcrit (word_id INTEGER NOT NULL, entry TEXT DEFAULT NULL, PRIMARY KEY (word_id)) STRICT;
             CREATE TABLE IF NOT EXISTS words (word_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, seq INTEGER NOT NULL, text_id INTEGER NOT NULL, word TEXT NOT NULL, gloss_id INTEGER DEFAULT NULL REFERENCES glosses (gloss_id), type INTEGER DEFAULT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, updatedUser TEXT NOT NULL DEFAULT '', isFlagged INTEGER NOT NULL DEFAULT 0, note TEXT NOT NULL DEFAULT '') STRICT;
             CREATE TABLE IF NOT EXISTS words_history (word_history_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, word_id INTEGER NOT NULL, seq INTEGER NOT NULL, text_id INTEGER NOT NULL, word TEXT NOT NULL, gloss_id INTEGER DEFAULT NULL REFERENCES glosses (gloss_id), type INTEGER DEFAULT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, updatedUser TEXT NOT NULL DEFAULT '', isFlagged INTEGER NOT NULL DEFAULT 0, note TEXT NOT NULL DEFAULT '') STRICT;
-            CREATE TABLE IF NOT EXISTS glosses_history (gloss_history_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, gloss_id INTEGER NOT NULL, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '') STRICT;
+            CREATE TABLE IF NOT EXISTS glosses_history (gloss_history_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, gloss_id INTEGER NOT NULL, unit INTEGER NOT NULL, lemma TEXT NOT NULL, sortalpha TEXT NOT NULL DEFAULT '', def TEXT NOT NULL, pos TEXT NOT NULL, note TEXT NOT NULL, updated TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, status INTEGER NOT NULL DEFAULT 1, updatedUser TEXT NOT NULL DEFAULT '', principal_parts TEXT, genitive TEXT, gender TEXT, terminations TEXT) STRICT;
             CREATE TABLE IF NOT EXISTS update_types (update_type_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, update_type TEXT NOT NULL) STRICT;
             CREATE TABLE IF NOT EXISTS "texts" (text_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, name TEXT NOT NULL, parent_id INTEGER references texts (text_id) DEFAULT NULL, display INTEGER DEFAULT 1, title TEXT NOT NULL DEFAULT '') STRICT;
+            CREATE TABLE IF NOT EXISTS texts_history (text_history_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, text_id INTEGER NOT NULL, name TEXT NOT NULL, parent_id INTEGER, display INTEGER, title TEXT NOT NULL, updated INTEGER, user_id INTEGER) STRICT;
             CREATE TABLE IF NOT EXISTS update_log (update_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, update_type INTEGER REFERENCES update_types(update_type_id), object_id INTEGER, history_id INTEGER, course_id INTEGER, update_desc TEXT, comment TEXT, updated INTEGER NOT NULL, user_id INTEGER REFERENCES users(user_id), ip TEXT, user_agent TEXT ) STRICT;
             CREATE TABLE IF NOT EXISTS users (user_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, name TEXT NOT NULL UNIQUE, initials TEXT NOT NULL UNIQUE, user_type INTEGER NOT NULL, password TEXT NOT NULL, email TEXT) STRICT;
             CREATE TABLE IF NOT EXISTS latex_page_breaks (word_id INTEGER NOT NULL UNIQUE REFERENCES words(word_id)) STRICT;
             CREATE TABLE IF NOT EXISTS containers (container_id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, name TEXT NOT NULL) STRICT;
+            CREATE TABLE IF NOT EXISTS course_x_container (course_id INTEGER NOT NULL REFERENCES courses (course_id), container_id INTEGER NOT NULL REFERENCES containers (container_id), container_order INTEGER NOT NULL, PRIMARY KEY (course_id, container_id)) STRICT;
+            CREATE TABLE IF NOT EXISTS container_x_text (course_id INTEGER NOT NULL, text_id INTEGER NOT NULL REFERENCES texts (text_id), container_id INTEGER NOT NULL, PRIMARY KEY (course_id, text_id), FOREIGN KEY (course_id, container_id) REFERENCES course_x_container (course_id, container_id)) STRICT;
             CREATE TABLE IF NOT EXISTS lemmatizer (form TEXT PRIMARY KEY NOT NULL, gloss_id INTEGER NOT NULL REFERENCES glosses(gloss_id)) STRICT;
 
             CREATE INDEX IF NOT EXISTS idx_hqvocab_lemma ON glosses (lemma);
@@ -1673,6 +3888,27 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             .await
             .map_err(map_sqlx_error)?;
 
+        for (table, column, column_type) in ADDED_COLUMNS {
+            let query = "SELECT COUNT(*) AS c FROM pragma_table_info($1) WHERE name = $2;";
+            let exists: i64 = sqlx::query(query)
+                .bind(table)
+                .bind(column)
+                .map(|rec: SqliteRow| rec.get("c"))
+                .fetch_one(&mut *self.tx)
+                .await
+                .map_err(map_sqlx_error)?;
+            if exists == 0 {
+                let query = format!(
+                    "ALTER TABLE {} ADD COLUMN {} {};",
+                    table, column, column_type
+                );
+                sqlx::query(&query)
+                    .execute(&mut *self.tx)
+                    .await
+                    .map_err(map_sqlx_error)?;
+            }
+        }
+
         //create default course
         let query = r#"REPLACE INTO courses VALUES (1, 'Greek');"#;
         sqlx::query(query)
@@ -1690,6 +3926,30 @@ impl GlosserDbTrx for GlosserDbSqliteTrx<'_> {
             (5, "Set gloss"),
             (6, "Import text"),
             (7, "Delete gloss"),
+            (8, "Edit course gloss"),
+            (9, "Edit tag"),
+            (10, "Tag gloss"),
+            (11, "Set gloss status"),
+            (12, "Import glosses"),
+            (13, "Purge gloss"),
+            (14, "Set gloss unit"),
+            (15, "Recompute sortalpha"),
+            (16, "Clone course"),
+            (17, "Set course baseline"),
+            (18, "Nest text"),
+            (19, "Edit container"),
+            (20, "Set text container"),
+            (21, "Attach text"),
+            (22, "Detach text"),
+            (23, "Edit text"),
+            (24, "Delete text"),
+            (25, "Split text"),
+            (26, "Merge texts"),
+            (27, "Edit word"),
+            (28, "Insert word"),
+            (29, "Delete word"),
+            (30, "Flag word"),
+            (31, "Unflag word"),
         ];
 
         for t in update_types {
@@ -0,0 +1,50 @@
+/*
+gkvocabdb
+
+Copyright (C) 2021  Jeremy March
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::GlossaryFormat;
+use crate::GlosserDb;
+use crate::GlosserError;
+use crate::map_json_error;
+
+pub(crate) fn map_csv_error(e: impl std::fmt::Display) -> GlosserError {
+    GlosserError::ExportError(e.to_string())
+}
+
+//dumps glosses in the same schema gkv_import_glosses reads, so they can be edited and re-imported
+pub async fn gkv_export_glosses(
+    db: &dyn GlosserDb,
+    course_id: Option<u32>,
+    format: GlossaryFormat,
+) -> Result<String, GlosserError> {
+    let mut tx = db.begin_tx().await?;
+    let rows = tx.get_glossary(course_id).await?;
+    tx.commit_tx().await?;
+
+    match format {
+        GlossaryFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            for row in &rows {
+                writer.serialize(row).map_err(map_csv_error)?;
+            }
+            let bytes = writer.into_inner().map_err(map_csv_error)?;
+            String::from_utf8(bytes).map_err(map_csv_error)
+        }
+        GlossaryFormat::Json => serde_json::to_string_pretty(&rows).map_err(map_json_error),
+    }
+}
@@ -21,12 +21,16 @@ along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::GlosserDb;
 use crate::GlosserError;
+use crate::TagFilter;
+use crate::UnapprovedGlosses;
 use crate::WordRow;
 use crate::WordType;
+use crate::mark_learned;
 use regex::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 struct Gloss {
@@ -48,6 +52,8 @@ pub async fn gkv_export_texts_as_latex(
     text_ids: &str,
     course_id: u32,
     bold_glosses: bool,
+    tags: &TagFilter,
+    unapproved: UnapprovedGlosses,
 ) -> Result<String, GlosserError> {
     let first_page_number = 24; //should be an even number, else headers will be reversed and we want page 1 to be a right hand page
     let even_page_header = "LGI - UPPER LEVEL GREEK";
@@ -66,12 +72,71 @@ pub async fn gkv_export_texts_as_latex(
 
     let mut tx = db.begin_tx().await?;
     let mut header = tx.get_text_title(*texts.first().unwrap()).await?; //this is overwritten for now by the title
+    let unapproved_glosses = if unapproved == UnapprovedGlosses::Allow {
+        vec![]
+    } else {
+        tx.get_unapproved_glosses(&texts).await?
+    };
     let mut words: Vec<WordRow> = vec![];
     for text_id in texts {
         words.append(&mut tx.get_words_for_export(text_id, course_id).await?);
     }
+    let included: Option<HashSet<u32>> = if tags.include.is_empty() {
+        None
+    } else {
+        Some(
+            tx.get_tagged_gloss_ids(&tags.include)
+                .await?
+                .into_iter()
+                .collect(),
+        )
+    };
+    let excluded: HashSet<u32> = tx
+        .get_tagged_gloss_ids(&tags.exclude)
+        .await?
+        .into_iter()
+        .collect();
+    let known: HashSet<u32> = tx
+        .get_known_gloss_ids(course_id)
+        .await?
+        .into_iter()
+        .collect();
     tx.commit_tx().await?;
 
+    mark_learned(&mut words, &known);
+
+    //words whose gloss is filtered out by tag are printed without a gloss
+    for w in words.iter_mut() {
+        if let Some(gloss_id) = w.hqid
+            && (excluded.contains(&gloss_id)
+                || included.as_ref().is_some_and(|i| !i.contains(&gloss_id)))
+        {
+            w.def = None;
+        }
+    }
+
+    //only glosses which will actually be printed matter here
+    let printed: HashSet<u32> = words
+        .iter()
+        .filter(|w| w.def.is_some())
+        .filter_map(|w| w.hqid)
+        .filter(|g| !known.contains(g))
+        .collect();
+    let unapproved_glosses: Vec<String> = unapproved_glosses
+        .into_iter()
+        .filter(|g| printed.contains(&g.gloss_id))
+        .map(|g| format!("{} ({})", g.lemma, g.status.name()))
+        .collect();
+    let mut warning = None;
+    if !unapproved_glosses.is_empty() {
+        let msg = format!("unapproved glosses: {}", unapproved_glosses.join(", "));
+        match unapproved {
+            UnapprovedGlosses::Refuse => return Err(GlosserError::ExportError(msg)),
+            UnapprovedGlosses::Warn => warning = Some(format!("% WARNING: {}\n", msg)),
+            UnapprovedGlosses::Allow => (),
+        }
+    }
+
     //divide words into seperate vectors of words per page
     let mut words_divided_by_page: Vec<Vec<WordRow>> = vec![];
 
@@ -305,10 +370,9 @@ pub async fn gkv_export_texts_as_latex(
             //last_seq = w.seq as i64;
             //last_word_id = w.wordid as i64;
 
-            if w.hqid.is_some()
-                && w.lemma.is_some()
+            if let (Some(gloss_id), Some(the_lemma)) = (w.hqid, w.lemma)
                 && w.def.is_some()
-                && !glosses.contains_key(&w.hqid.unwrap())
+                && !glosses.contains_key(&gloss_id)
             {
                 // if (!is_null($row["arrowedSeq"]) && (int)$row["seq"] > (int)$row["arrowedSeq"]) {
                 //     //echo $row["seq"] . ", " . $row["arrowedSeq"] . "\n";
@@ -321,8 +385,6 @@ pub async fn gkv_export_texts_as_latex(
                 // else {
                 //     $g->arrow = FALSE;
                 // }
-                let the_lemma = w.lemma.unwrap();
-                let gloss_id = w.hqid.unwrap();
                 let the_sort_alpha = w.sort_alpha.unwrap_or(String::from(""));
 
                 let is_arrowed;
@@ -473,6 +535,9 @@ pub async fn gkv_export_texts_as_latex(
     }
 
     latex.push_str("\\end{document}\n");
+    if let Some(warning) = warning {
+        latex.insert_str(0, &warning);
+    }
     Ok(latex)
 }
 
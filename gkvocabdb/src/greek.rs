@@ -0,0 +1,129 @@
+/*
+gkvocabdb
+
+Copyright (C) 2021  Jeremy March
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use regex::Regex;
+use std::sync::LazyLock;
+use unicode_normalization::UnicodeNormalization;
+
+//articles which may precede a lemma, compared after diacritics are stripped
+const ARTICLES: [&str; 11] = [
+    "ο", "η", "το", "οι", "αι", "τα", "του", "της", "των", "τον", "την",
+];
+
+//variant letter forms which sort as their ordinary letter
+fn base_letter(c: char) -> char {
+    match c {
+        'ϐ' => 'β',
+        'ϑ' => 'θ',
+        'ϕ' => 'φ',
+        'ϖ' => 'π',
+        'ϰ' => 'κ',
+        'ϱ' => 'ρ',
+        'ϲ' | 'ς' => 'σ',
+        'ϵ' => 'ε',
+        _ => c,
+    }
+}
+
+//New Athena Unicode (static/newathu5_8) puts letters with macrons, breves and other
+//combinations Unicode lacks in the private use area. They have no decomposition, so map them
+//to their base letter here; None for its stand-alone diacritic glyphs
+fn pua_base_letter(c: char) -> Option<char> {
+    match c {
+        '\u{E1A9}' | '\u{E1B0}'..='\u{E1BF}' | '\u{EB20}'..='\u{EB27}' => Some('ε'),
+        '\u{EC73}'..='\u{EC7A}' => Some('ε'),
+        '\u{E1AA}' | '\u{E1C0}'..='\u{E1CF}' | '\u{EB5A}'..='\u{EB5C}' => Some('ο'),
+        '\u{EC7B}'..='\u{EC82}' => Some('ο'),
+        '\u{E1AB}' => Some('ρ'),
+        '\u{E1AD}'..='\u{E1AF}' | '\u{EB6F}'..='\u{EB8A}' => Some('υ'),
+        '\u{EAF8}' | '\u{EB09}' | '\u{EC00}'..='\u{EC08}' => Some('α'),
+        '\u{EB40}'..='\u{EB48}' => Some('ι'),
+        '\u{EC09}'..='\u{EC11}' => Some('η'),
+        '\u{EC12}'..='\u{EC1A}' => Some('ω'),
+        '\u{EC20}'..='\u{EC3A}' => None,
+        _ => Some(c),
+    }
+}
+
+//removes diacritics and maps variant letters to their base letter
+fn strip_marks(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .filter_map(pua_base_letter)
+        .map(base_letter)
+}
+
+//lowercases and removes diacritics, leaving any punctuation
+pub fn strip_diacritics(s: &str) -> String {
+    strip_marks(s)
+        .flat_map(char::to_lowercase)
+        .map(base_letter)
+        .collect()
+}
+
+//normalizes a regex to match against sortalpha; not lowercased, since that would change
+//the meaning of classes like \S or \W
+pub fn strip_pattern(s: &str) -> String {
+    strip_marks(s).collect()
+}
+
+fn strip_word(word: &str) -> String {
+    strip_diacritics(word)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+fn strip_words(s: &str) -> Vec<String> {
+    s.split_whitespace()
+        .map(strip_word)
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+static PARENS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\([^)]*\)|\[[^\]]*\]").unwrap());
+
+//the key glosses are sorted and searched by: the first headword of the lemma without accents,
+//breathings, punctuation, parenthesised material or a leading article, e.g. "ὁ λόγος, -ου" -> "λογος"
+pub fn make_sortalpha(lemma: &str) -> String {
+    let lemma = lemma.nfc().collect::<String>();
+    let without_parens = PARENS_RE.replace_all(&lemma, " ");
+    let headword = without_parens.split(',').next().unwrap_or("");
+
+    let mut words = strip_words(headword);
+    while words.len() > 1 && ARTICLES.contains(&words[0].as_str()) {
+        words.remove(0);
+    }
+    if words.is_empty() {
+        //e.g. a lemma which is entirely parenthesised or punctuation
+        words = strip_words(&lemma);
+    }
+    words.join(" ")
+}
+
+//key whose byte order is Greek dictionary order: base letters first, then the decomposed
+//diacritics break ties. Stored as glosses.sortkey so both backends order glosses by comparing
+//bytes, SQLite with its default BINARY collation and Postgres with COLLATE "C"
+pub fn sort_key(s: &str) -> String {
+    format!(
+        "{}\u{1}{}",
+        strip_diacritics(s),
+        s.nfd().collect::<String>()
+    )
+}
@@ -0,0 +1,260 @@
+/*
+gkvocabdb
+
+Copyright (C) 2021  Jeremy March
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::ConnectionInfo;
+use crate::GlossImportOutcome;
+use crate::GlossImportReport;
+use crate::GlossImportRowResult;
+use crate::GlossStatus;
+use crate::GlossaryFormat;
+use crate::GlossaryRow;
+use crate::GlosserDb;
+use crate::GlosserError;
+use crate::UpdateType;
+use crate::greek::make_sortalpha;
+use crate::is_valid_unit;
+use unicode_normalization::UnicodeNormalization;
+
+impl GlossImportReport {
+    fn push(
+        &mut self,
+        row: usize,
+        lemma: &str,
+        gloss_id: Option<u32>,
+        outcome: GlossImportOutcome,
+        reason: Option<&str>,
+    ) {
+        match outcome {
+            GlossImportOutcome::Inserted => self.inserted += 1,
+            GlossImportOutcome::Updated => self.updated += 1,
+            GlossImportOutcome::Skipped => self.skipped += 1,
+        }
+        self.rows.push(GlossImportRowResult {
+            row,
+            lemma: lemma.to_string(),
+            gloss_id,
+            outcome,
+            reason: reason.map(|r| r.to_string()),
+        });
+    }
+}
+
+//(the row's lemma as written, why the row was skipped)
+type RowError = (String, String);
+
+//rows which fail to parse are kept as errors, with whatever lemma they have, so they can be
+//reported and skipped
+fn parse_rows(
+    data: &str,
+    format: GlossaryFormat,
+) -> Result<Vec<Result<GlossaryRow, RowError>>, GlosserError> {
+    match format {
+        GlossaryFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(data.as_bytes());
+            let headers = reader
+                .headers()
+                .map_err(|e| GlosserError::ImportError(format!("Error importing glosses: {}", e)))?
+                .clone();
+            let lemma_idx = headers.iter().position(|h| h == "lemma");
+            Ok(reader
+                .records()
+                .map(|r| {
+                    let record = r.map_err(|e| (String::from(""), e.to_string()))?;
+                    let lemma = lemma_idx.and_then(|i| record.get(i)).unwrap_or("");
+                    record
+                        .deserialize::<GlossaryRow>(Some(&headers))
+                        .map_err(|e| (lemma.to_string(), e.to_string()))
+                })
+                .collect())
+        }
+        GlossaryFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_str(data).map_err(|e| {
+                GlosserError::ImportError(format!("Error importing glosses: {}", e))
+            })?;
+            Ok(values
+                .into_iter()
+                .map(|v| {
+                    let lemma = v
+                        .get("lemma")
+                        .and_then(|l| l.as_str())
+                        .unwrap_or("")
+                        .to_string();
+                    serde_json::from_value::<GlossaryRow>(v).map_err(|e| (lemma, e.to_string()))
+                })
+                .collect())
+        }
+    }
+}
+
+fn validate_row(row: GlossaryRow) -> Result<GlossaryRow, String> {
+    let lemma = row.lemma.trim().nfc().collect::<String>();
+    if lemma.is_empty() {
+        return Err(String::from("lemma is empty"));
+    }
+    let def = row.def.trim().to_string();
+    if def.is_empty() {
+        return Err(String::from("definition is empty"));
+    }
+    if let Some(unit) = row.unit
+        && !is_valid_unit(unit)
+    {
+        return Err(format!("invalid unit {}", unit));
+    }
+    //deleting goes through delete_gloss, which refuses glosses still attached to words
+    if row.status == Some(GlossStatus::Deleted) {
+        return Err(String::from("status deleted cannot be imported"));
+    }
+    //any sortalpha in the file is ignored in favour of the one derived from the lemma
+    let sortalpha = make_sortalpha(&lemma);
+    let parts = row.parts();
+
+    Ok(GlossaryRow {
+        lemma,
+        sortalpha,
+        pos: row.pos.trim().to_string(),
+        def,
+        note: row.note.trim().to_string(),
+        principal_parts: parts.principal_parts,
+        genitive: parts.genitive,
+        gender: parts.gender,
+        terminations: parts.terminations,
+        ..row
+    })
+}
+
+pub async fn gkv_import_glosses(
+    db: &dyn GlosserDb,
+    data: &str,
+    format: GlossaryFormat,
+    info: &ConnectionInfo,
+) -> Result<GlossImportReport, GlosserError> {
+    let rows = parse_rows(data, format)?;
+    if rows.is_empty() {
+        return Err(GlosserError::ImportError(String::from(
+            "Error importing glosses: File is empty.",
+        )));
+    }
+
+    let mut report = GlossImportReport {
+        inserted: 0,
+        updated: 0,
+        skipped: 0,
+        rows: vec![],
+    };
+
+    let mut tx = db.begin_tx().await?;
+    for (idx, row) in rows.into_iter().enumerate() {
+        let n = idx + 1;
+        let row = match row.and_then(|row| {
+            let lemma = row.lemma.trim().to_string();
+            validate_row(row).map_err(|reason| (lemma, reason))
+        }) {
+            Ok(row) => row,
+            Err((lemma, reason)) => {
+                report.push(n, &lemma, None, GlossImportOutcome::Skipped, Some(&reason));
+                continue;
+            }
+        };
+
+        let existing = match row.gloss_id {
+            Some(gloss_id) => match tx.get_glossary_row(gloss_id).await? {
+                Some(g) => vec![g],
+                None => {
+                    report.push(
+                        n,
+                        &row.lemma,
+                        Some(gloss_id),
+                        GlossImportOutcome::Skipped,
+                        Some("gloss_id not found"),
+                    );
+                    continue;
+                }
+            },
+            None => tx.find_glossary_rows(&row.lemma, &row.sortalpha).await?,
+        };
+
+        match existing.as_slice() {
+            [] => {
+                let gloss_id = tx.insert_glossary_row(&row, info).await?;
+                report.push(
+                    n,
+                    &row.lemma,
+                    Some(gloss_id),
+                    GlossImportOutcome::Inserted,
+                    None,
+                );
+            }
+            [old] => {
+                let new = GlossaryRow {
+                    gloss_id: old.gloss_id,
+                    unit: row.unit.or(old.unit),
+                    status: row.status.or(old.status),
+                    ..row
+                };
+                if new == *old {
+                    report.push(
+                        n,
+                        &new.lemma,
+                        new.gloss_id,
+                        GlossImportOutcome::Skipped,
+                        Some("unchanged"),
+                    );
+                } else {
+                    let gloss_id = old.gloss_id.unwrap();
+                    tx.update_glossary_row(gloss_id, &new, info).await?;
+                    report.push(
+                        n,
+                        &new.lemma,
+                        Some(gloss_id),
+                        GlossImportOutcome::Updated,
+                        None,
+                    );
+                }
+            }
+            _ => {
+                report.push(
+                    n,
+                    &row.lemma,
+                    None,
+                    GlossImportOutcome::Skipped,
+                    Some("matches more than one gloss"),
+                );
+            }
+        }
+    }
+
+    tx.update_log_trx(
+        UpdateType::ImportGlosses,
+        None,
+        None,
+        None,
+        format!(
+            "Imported glosses: {} inserted, {} updated, {} skipped",
+            report.inserted, report.updated, report.skipped
+        )
+        .as_str(),
+        info,
+    )
+    .await?;
+    tx.commit_tx().await?;
+
+    Ok(report)
+}
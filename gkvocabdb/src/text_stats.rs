@@ -0,0 +1,120 @@
+/*
+gkvocabdb
+
+Copyright (C) 2021  Jeremy March
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::GlosserDb;
+use crate::GlosserError;
+use crate::TextGlossCount;
+use crate::TextStats;
+use crate::export_glosses::map_csv_error;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+
+//rows come in course order, one per gloss in each text, so a gloss is new the first time it is seen.
+//Glosses in the course baseline or arrowed at H&Q are already learned, so never new
+fn text_stats(rows: &[TextGlossCount], known: &HashSet<u32>) -> Vec<TextStats> {
+    let mut seen = HashSet::new();
+    let mut stats: Vec<TextStats> = vec![];
+    for r in rows {
+        if stats.last().is_none_or(|s| s.text_id != r.text_id) {
+            stats.push(TextStats {
+                text_id: r.text_id,
+                text_name: r.text_name.clone(),
+                ..Default::default()
+            });
+        }
+        let s = stats.last_mut().unwrap();
+        s.tokens += r.tokens;
+        let Some(gloss_id) = r.gloss_id else {
+            s.unglossed_tokens += r.tokens;
+            continue;
+        };
+        s.distinct_glosses += 1;
+        if r.arrowed_hq || known.contains(&gloss_id) {
+            s.arrowed_earlier += 1;
+        } else {
+            if seen.insert(gloss_id) {
+                s.new_glosses += 1;
+            }
+            if r.arrowed_text_order.is_some_and(|o| o < r.text_order) {
+                s.arrowed_earlier += 1;
+            }
+        }
+        *s.units.entry(r.unit.unwrap_or(0)).or_default() += 1;
+    }
+    stats
+}
+
+pub async fn gkv_get_text_stats(
+    db: &dyn GlosserDb,
+    course_id: u32,
+) -> Result<Vec<TextStats>, GlosserError> {
+    let mut tx = db.begin_tx().await?;
+    let rows = tx.get_text_gloss_counts(course_id).await?;
+    let known: HashSet<u32> = tx
+        .get_known_gloss_ids(course_id)
+        .await?
+        .into_iter()
+        .collect();
+    tx.commit_tx().await?;
+    Ok(text_stats(&rows, &known))
+}
+
+//one row per text, with a unit_N column for each unit used anywhere in the course
+pub async fn gkv_export_text_stats(
+    db: &dyn GlosserDb,
+    course_id: u32,
+) -> Result<String, GlosserError> {
+    let stats = gkv_get_text_stats(db, course_id).await?;
+    let units: BTreeSet<u32> = stats.iter().flat_map(|s| s.units.keys().copied()).collect();
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+    let header = [
+        "text_id",
+        "text_name",
+        "tokens",
+        "distinct_glosses",
+        "new_glosses",
+        "arrowed_earlier",
+        "unglossed_tokens",
+    ]
+    .map(String::from)
+    .into_iter()
+    .chain(units.iter().map(|u| format!("unit_{}", u)));
+    writer.write_record(header).map_err(map_csv_error)?;
+    for s in &stats {
+        let record = [
+            s.text_id.to_string(),
+            s.text_name.clone(),
+            s.tokens.to_string(),
+            s.distinct_glosses.to_string(),
+            s.new_glosses.to_string(),
+            s.arrowed_earlier.to_string(),
+            s.unglossed_tokens.to_string(),
+        ]
+        .into_iter()
+        .chain(
+            units
+                .iter()
+                .map(|u| s.units.get(u).copied().unwrap_or(0).to_string()),
+        );
+        writer.write_record(record).map_err(map_csv_error)?;
+    }
+    let bytes = writer.into_inner().map_err(map_csv_error)?;
+    String::from_utf8(bytes).map_err(map_csv_error)
+}